@@ -0,0 +1,73 @@
+use clustr::metric::{Levenshtein, LevenshteinKernel, Metric};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use edit_distance::edit_distance;
+use std::hint::black_box;
+
+const MAX_EDIT_FRAC: f32 = 0.2;
+
+// Thresholding as performed before the kernels were introduced
+fn edit_distance_is_similar(a: &str, b: &str, tol: f32) -> bool {
+    let (len_a, len_b) = (a.chars().count(), b.chars().count());
+    let max_edit = (len_a.min(len_b) as f32 * tol) as usize;
+    if len_a.abs_diff(len_b) > max_edit {
+        return false;
+    }
+    edit_distance(a, b) <= max_edit
+}
+
+// Deterministic strings over a small alphabet, so that strings are rarely similar
+// but share many characters
+fn generate(n: usize, len: usize) -> Vec<String> {
+    let mut state: u64 = 42;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+    (0..n)
+        .map(|_| (0..len).map(|_| ['a', 'c', 'g', 't'][next() % 4]).collect())
+        .collect()
+}
+
+fn bench_is_similar(c: &mut Criterion) {
+    let mut group = c.benchmark_group("is_similar");
+
+    for len in [16, 64, 256] {
+        let inputs = generate(50, len);
+        let pairs: Vec<(&str, &str)> = inputs
+            .iter()
+            .flat_map(|a| inputs.iter().map(move |b| (a.as_str(), b.as_str())))
+            .collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("edit_distance", len),
+            &pairs,
+            |bench, pairs| {
+                bench.iter(|| {
+                    for (a, b) in pairs {
+                        black_box(edit_distance_is_similar(a, b, MAX_EDIT_FRAC));
+                    }
+                })
+            },
+        );
+
+        for (name, kernel) in [
+            ("bit_parallel", LevenshteinKernel::BitParallel),
+            ("banded", LevenshteinKernel::Banded),
+        ] {
+            let metric = Levenshtein::new(MAX_EDIT_FRAC).unwrap().with_kernel(kernel);
+            group.bench_with_input(BenchmarkId::new(name, len), &pairs, |bench, pairs| {
+                bench.iter(|| {
+                    for &(a, b) in pairs {
+                        black_box(metric.is_similar(a, b));
+                    }
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_is_similar);
+criterion_main!(benches);
@@ -1,4 +1,9 @@
+pub mod agglomerative;
 pub mod cluster;
+pub mod dbscan;
+pub mod disjoint_set;
+pub mod distance_matrix;
+pub mod kmedoids;
 pub mod merge;
 
-pub use crate::metric::similarity::is_similar;
+pub use crate::metric::Metric;
@@ -0,0 +1,209 @@
+use super::disjoint_set::DisjointSet;
+use super::distance_matrix::DistanceMatrix;
+use crate::hierarchy::{Cut, Linkage};
+
+// Merge of the clusters represented by inputs `a` and `b` at `distance`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Merge {
+    pub a: usize,
+    pub b: usize,
+    pub distance: f64,
+}
+
+// Build the full merge hierarchy with the nearest-neighbour chain algorithm,
+// which needs O(n^2) time for linkages satisfying the reducibility property.
+// Merges are returned in order of increasing distance.
+pub fn agglomerate(mut matrix: DistanceMatrix, linkage: Linkage) -> Vec<Merge> {
+    let n = matrix.len();
+    let mut size = vec![1usize; n];
+    let mut active = vec![true; n];
+    let mut chain: Vec<usize> = Vec::new();
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+
+    for _ in 1..n {
+        if chain.is_empty() {
+            chain.push(active.iter().position(|&a| a).expect("two active clusters"));
+        }
+
+        // Grow the chain of nearest neighbours until two clusters are each
+        // other's nearest neighbours
+        let (x, y) = loop {
+            let x = chain[chain.len() - 1];
+            // Prefer the previous link on ties so that the chain cannot cycle
+            let previous = chain.len().checked_sub(2).map(|i| chain[i]);
+            let mut nearest = previous;
+            let mut min = previous.map_or(f64::INFINITY, |p| matrix.get(x, p));
+
+            for i in (0..n).filter(|&i| active[i] && i != x) {
+                let distance = matrix.get(x, i);
+                if distance < min || nearest.is_none() {
+                    nearest = Some(i);
+                    min = distance;
+                }
+            }
+
+            let y = nearest.expect("two active clusters");
+            if Some(y) == previous {
+                break (x, y);
+            }
+            chain.push(y);
+        };
+        chain.truncate(chain.len() - 2);
+
+        // The merged cluster is represented by `y`
+        let distance = matrix.get(x, y);
+        for k in (0..n).filter(|&k| active[k] && k != x && k != y) {
+            let updated = update(
+                linkage,
+                (matrix.get(x, k), matrix.get(y, k), distance),
+                (size[x], size[y], size[k]),
+            );
+            matrix.set(y, k, updated);
+        }
+        active[x] = false;
+        size[y] += size[x];
+        merges.push(Merge {
+            a: x.min(y),
+            b: x.max(y),
+            distance,
+        });
+    }
+
+    merges.sort_by(|m, o| m.distance.total_cmp(&o.distance));
+    merges
+}
+
+// Lance-Williams update of the distance between cluster k and the union of
+// clusters x and y
+fn update(
+    linkage: Linkage,
+    (d_xk, d_yk, d_xy): (f64, f64, f64),
+    (n_x, n_y, n_k): (usize, usize, usize),
+) -> f64 {
+    let (n_x, n_y, n_k) = (n_x as f64, n_y as f64, n_k as f64);
+    match linkage {
+        Linkage::Single => d_xk.min(d_yk),
+        Linkage::Complete => d_xk.max(d_yk),
+        Linkage::Average => (n_x * d_xk + n_y * d_yk) / (n_x + n_y),
+        // The merged pair is never farther apart than either is from k, so an
+        // infinite d_xy implies infinite d_xk and d_yk. Subtracting it would
+        // give inf - inf.
+        Linkage::Ward if d_xk.is_infinite() || d_yk.is_infinite() => f64::INFINITY,
+        Linkage::Ward => {
+            let squared = (n_x + n_k) * d_xk * d_xk + (n_y + n_k) * d_yk * d_yk - n_k * d_xy * d_xy;
+            (squared / (n_x + n_y + n_k)).sqrt()
+        }
+    }
+}
+
+// Apply the merges selected by `cut` to n singleton clusters
+pub fn cut_merges(n: usize, merges: &[Merge], cut: Cut) -> DisjointSet {
+    let n_merges = match cut {
+        Cut::Distance(max) => merges.iter().take_while(|m| m.distance <= max).count(),
+        Cut::Clusters(k) => n - k,
+    };
+
+    let mut clusters = DisjointSet::new(n);
+    for merge in &merges[..n_merges] {
+        clusters.union(merge.a, merge.b);
+    }
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{agglomerate, cut_merges, DistanceMatrix, Merge};
+    use crate::hierarchy::{Cut, Linkage};
+
+    // Points 0, 1, 3 and 10 on a line
+    fn line() -> DistanceMatrix {
+        DistanceMatrix::new(4, vec![1.0, 3.0, 10.0, 2.0, 9.0, 7.0])
+    }
+
+    fn distances(merges: &[Merge]) -> Vec<f64> {
+        merges.iter().map(|m| m.distance).collect()
+    }
+
+    mod agglomerate {
+        use super::{agglomerate, distances, line, DistanceMatrix, Merge};
+        use crate::hierarchy::Linkage;
+
+        #[test]
+        fn single_linkage_merges_nearest_members() {
+            let merges = agglomerate(line(), Linkage::Single);
+            assert_eq!(distances(&merges), vec![1.0, 2.0, 7.0]);
+            assert_eq!(
+                merges[0],
+                Merge {
+                    a: 0,
+                    b: 1,
+                    distance: 1.0
+                }
+            );
+        }
+
+        #[test]
+        fn complete_linkage_merges_farthest_members() {
+            let merges = agglomerate(line(), Linkage::Complete);
+            assert_eq!(distances(&merges), vec![1.0, 3.0, 10.0]);
+        }
+
+        #[test]
+        fn average_linkage_merges_mean_distance() {
+            let merges = agglomerate(line(), Linkage::Average);
+            assert_eq!(distances(&merges), vec![1.0, 2.5, 26.0 / 3.0]);
+        }
+
+        #[test]
+        fn ward_linkage_merges_by_variance_increase() {
+            let merges = agglomerate(line(), Linkage::Ward);
+            // d({0,1},3) = sqrt((2 * 9 + 2 * 4 - 1) / 3)
+            let expected = (25.0f64 / 3.0).sqrt();
+            assert_eq!(merges[0].distance, 1.0);
+            assert!((merges[1].distance - expected).abs() < 1e-9);
+        }
+
+        #[test]
+        fn handles_single_input() {
+            assert!(agglomerate(DistanceMatrix::new(1, vec![]), Linkage::Average).is_empty());
+        }
+
+        #[test]
+        fn infinite_distances_merged_last() {
+            let matrix = DistanceMatrix::new(3, vec![f64::INFINITY, f64::INFINITY, 1.0]);
+            let merges = agglomerate(matrix, Linkage::Complete);
+            assert_eq!(distances(&merges), vec![1.0, f64::INFINITY]);
+        }
+
+        #[test]
+        fn infinite_distances_merged_last_by_ward_linkage() {
+            // Two pairs at distance 1, infinitely far from one another
+            let inf = f64::INFINITY;
+            let matrix = DistanceMatrix::new(4, vec![1.0, inf, inf, inf, inf, 1.0]);
+            let merges = agglomerate(matrix, Linkage::Ward);
+            assert_eq!(distances(&merges), vec![1.0, 1.0, inf]);
+        }
+    }
+
+    mod cut_merges {
+        use super::{agglomerate, cut_merges, line, Cut, Linkage};
+
+        #[test]
+        fn distance_cut_applied() {
+            let merges = agglomerate(line(), Linkage::Complete);
+            let mut clusters = cut_merges(4, &merges, Cut::Distance(3.0));
+            assert_eq!(clusters.sets(), vec![vec![0, 1, 2], vec![3]]);
+            let mut clusters = cut_merges(4, &merges, Cut::Distance(2.9));
+            assert_eq!(clusters.sets(), vec![vec![0, 1], vec![2], vec![3]]);
+        }
+
+        #[test]
+        fn cluster_count_cut_applied() {
+            let merges = agglomerate(line(), Linkage::Single);
+            assert_eq!(cut_merges(4, &merges, Cut::Clusters(4)).sets().len(), 4);
+            let mut clusters = cut_merges(4, &merges, Cut::Clusters(2));
+            assert_eq!(clusters.sets(), vec![vec![0, 1, 2], vec![3]]);
+            assert_eq!(cut_merges(4, &merges, Cut::Clusters(1)).sets().len(), 1);
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use super::is_similar;
+use super::Metric;
 
-pub fn cluster<'a>(inputs: &[&'a str], max_edit_frac: f32) -> Vec<Vec<&'a str>> {
+pub fn cluster<'a, S, M>(inputs: &[&'a S], metric: &M) -> Vec<Vec<&'a S>>
+where
+    S: ?Sized,
+    M: Metric<S>,
+{
     let mut container = init_container(inputs);
 
     // Store if value has been moved into a cluster
@@ -18,7 +22,7 @@ pub fn cluster<'a>(inputs: &[&'a str], max_edit_frac: f32) -> Vec<Vec<&'a str>>
         }
 
         for j in i + 1..inputs.len() {
-            if is_similar(container[i][0], container[j][0], max_edit_frac) {
+            if metric.is_similar(container[i][0], container[j][0]) {
                 let str_ref = container[j][0];
                 container[i].push(str_ref);
                 moved[j] = true;
@@ -35,7 +39,7 @@ pub fn cluster<'a>(inputs: &[&'a str], max_edit_frac: f32) -> Vec<Vec<&'a str>>
     container
 }
 
-fn init_container<'a>(inputs: &[&'a str]) -> Vec<Vec<&'a str>> {
+fn init_container<'a, S: ?Sized>(inputs: &[&'a S]) -> Vec<Vec<&'a S>> {
     let mut container = vec![Vec::new(); inputs.len()];
 
     for (i, s) in inputs.iter().enumerate() {
@@ -50,12 +54,13 @@ mod tests {
 
     mod clusters {
         use super::cluster;
+        use crate::metric::Levenshtein;
 
         #[test]
         fn test_cluster_correct() {
             let inputs = vec!["a", "a", "b", "b"];
             let expected = vec![vec!["a", "a"], vec!["b", "b"]];
-            let results = cluster(&inputs, 0.0);
+            let results = cluster(&inputs, &Levenshtein::new(0.0).unwrap());
             assert_eq!(results, expected);
         }
 
@@ -63,7 +68,7 @@ mod tests {
         fn test_clusters_formed_below_max_edit_frac() {
             let inputs = vec!["aaa", "aac", "bbb", "bbc"];
             let expected = vec![vec!["aaa", "aac"], vec!["bbb", "bbc"]];
-            let results = cluster(&inputs, 0.34);
+            let results = cluster(&inputs, &Levenshtein::new(0.34).unwrap());
             assert_eq!(results, expected);
         }
 
@@ -71,7 +76,15 @@ mod tests {
         fn test_clusters_formed_equal_max_edit_frac() {
             let inputs = vec!["aa", "ab", "cc", "cd"];
             let expected = vec![vec!["aa", "ab"], vec!["cc", "cd"]];
-            let results = cluster(&inputs, 0.5);
+            let results = cluster(&inputs, &Levenshtein::new(0.5).unwrap());
+            assert_eq!(results, expected);
+        }
+
+        #[test]
+        fn test_clusters_sequences() {
+            let inputs: Vec<&[u8]> = vec![b"aaa", b"bbb", b"aab"];
+            let expected: Vec<Vec<&[u8]>> = vec![vec![b"aaa", b"aab"], vec![b"bbb"]];
+            let results = cluster(&inputs, &Levenshtein::new(0.34).unwrap());
             assert_eq!(results, expected);
         }
 
@@ -79,7 +92,7 @@ mod tests {
         fn test_no_clusters() {
             let inputs = vec!["a", "b", "c"];
             let expected = vec![vec!["a"], vec!["b"], vec!["c"]];
-            let results = cluster(&inputs, 0.0);
+            let results = cluster(&inputs, &Levenshtein::new(0.0).unwrap());
             assert_eq!(results, expected);
         }
     }
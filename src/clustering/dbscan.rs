@@ -0,0 +1,92 @@
+use super::disjoint_set::DisjointSet;
+
+// Density-based clustering of n inputs given each input's similar neighbours.
+// Inputs with at least `min_samples` neighbours, counting themselves, are core
+// inputs. Connected core inputs form clusters, and every other input joins the
+// cluster of its first core neighbour, if any, or is noise. Returns the
+// clusters, ordered by their smallest index, and the noise indices.
+pub fn dbscan(neighbours: &[Vec<usize>], min_samples: usize) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let n = neighbours.len();
+    let core: Vec<bool> = neighbours
+        .iter()
+        .map(|adjacent| adjacent.len() + 1 >= min_samples)
+        .collect();
+
+    let mut forest = DisjointSet::new(n);
+    for i in (0..n).filter(|&i| core[i]) {
+        for &j in neighbours[i].iter().filter(|&&j| core[j]) {
+            forest.union(i, j);
+        }
+    }
+
+    let mut cluster_of_root = vec![usize::MAX; n];
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    let mut noise = Vec::new();
+
+    for i in 0..n {
+        let anchor = if core[i] {
+            Some(i)
+        } else {
+            neighbours[i].iter().copied().filter(|&j| core[j]).min()
+        };
+
+        match anchor {
+            Some(anchor) => {
+                let root = forest.find(anchor);
+                if cluster_of_root[root] == usize::MAX {
+                    cluster_of_root[root] = clusters.len();
+                    clusters.push(Vec::new());
+                }
+                clusters[cluster_of_root[root]].push(i);
+            }
+            None => noise.push(i),
+        }
+    }
+    (clusters, noise)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dbscan;
+
+    // Adjacency lists from an edge list
+    fn neighbours(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<usize>> {
+        let mut neighbours = vec![Vec::new(); n];
+        for &(i, j) in edges {
+            neighbours[i].push(j);
+            neighbours[j].push(i);
+        }
+        neighbours
+    }
+
+    #[test]
+    fn isolated_inputs_are_noise() {
+        let graph = neighbours(5, &[(0, 1), (1, 2), (0, 2)]);
+        let (clusters, noise) = dbscan(&graph, 3);
+        assert_eq!(clusters, vec![vec![0, 1, 2]]);
+        assert_eq!(noise, vec![3, 4]);
+    }
+
+    #[test]
+    fn border_inputs_join_without_linking() {
+        // 2 borders both the core clique {0, 1, 5, 6} and the core clique
+        // {3, 4, 7, 8}, but is not core itself, so the cliques remain separate
+        let mut edges = vec![(1, 2), (2, 3)];
+        for clique in [[0, 1, 5, 6], [3, 4, 7, 8]] {
+            for (k, &i) in clique.iter().enumerate() {
+                edges.extend(clique[k + 1..].iter().map(|&j| (i, j)));
+            }
+        }
+        let (clusters, noise) = dbscan(&neighbours(9, &edges), 4);
+        assert_eq!(clusters, vec![vec![0, 1, 2, 5, 6], vec![3, 4, 7, 8]]);
+        assert!(noise.is_empty());
+    }
+
+    #[test]
+    fn single_sample_clusters_every_input() {
+        let graph = neighbours(3, &[(0, 2)]);
+        let (clusters, noise) = dbscan(&graph, 1);
+        assert_eq!(clusters, vec![vec![0, 2], vec![1]]);
+        assert!(noise.is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+// Disjoint-set forest over the indices 0..n, with union by size and path halving
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> DisjointSet {
+        DisjointSet {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    // Returns false if `x` and `y` were already in the same set
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        true
+    }
+
+    // Add every union of `other` to this forest
+    pub fn absorb(&mut self, other: &mut DisjointSet) {
+        for x in 0..other.len() {
+            let root = other.find(x);
+            if root != x {
+                self.union(x, root);
+            }
+        }
+    }
+
+    // Indices of each set, with sets ordered by their smallest index
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut set_of_root = vec![usize::MAX; self.len()];
+        let mut sets: Vec<Vec<usize>> = Vec::new();
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if set_of_root[root] == usize::MAX {
+                set_of_root[root] = sets.len();
+                sets.push(Vec::new());
+            }
+            sets[set_of_root[root]].push(x);
+        }
+        sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn singletons_by_default() {
+        let mut set = DisjointSet::new(3);
+        assert_eq!(set.sets(), vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn union_transitive() {
+        let mut set = DisjointSet::new(5);
+        assert!(set.union(0, 3));
+        assert!(set.union(3, 4));
+        assert!(!set.union(4, 0));
+        assert_eq!(set.find(0), set.find(4));
+        assert_eq!(set.sets(), vec![vec![0, 3, 4], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn absorb_merges_forests() {
+        let mut a = DisjointSet::new(4);
+        let mut b = DisjointSet::new(4);
+        a.union(0, 1);
+        b.union(1, 3);
+        a.absorb(&mut b);
+        assert_eq!(a.sets(), vec![vec![0, 1, 3], vec![2]]);
+    }
+}
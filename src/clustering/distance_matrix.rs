@@ -0,0 +1,70 @@
+// Pairwise distances between n inputs, stored as the upper triangle of the
+// symmetric distance matrix without its diagonal
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMatrix {
+    n: usize,
+    distances: Vec<f64>,
+}
+
+impl DistanceMatrix {
+    // `distances` lists the pairs (0, 1), (0, 2), ..., (0, n - 1), (1, 2), ...
+    pub fn new(n: usize, distances: Vec<f64>) -> DistanceMatrix {
+        debug_assert_eq!(distances.len(), n * n.saturating_sub(1) / 2);
+        DistanceMatrix { n, distances }
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        if i == j {
+            return 0.0;
+        }
+        self.distances[self.index(i, j)]
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, distance: f64) {
+        let index = self.index(i, j);
+        self.distances[index] = distance;
+    }
+
+    // Offset of the first pair in row `i`
+    pub fn row_start(n: usize, i: usize) -> usize {
+        n * i - i * (i + 1) / 2
+    }
+
+    fn index(&self, i: usize, j: usize) -> usize {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        DistanceMatrix::row_start(self.n, i) + j - i - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DistanceMatrix;
+
+    #[test]
+    fn pairs_indexed_symmetrically() {
+        let matrix = DistanceMatrix::new(4, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(matrix.get(0, 1), 1.0);
+        assert_eq!(matrix.get(3, 0), 3.0);
+        assert_eq!(matrix.get(1, 2), 4.0);
+        assert_eq!(matrix.get(2, 3), 6.0);
+        assert_eq!(matrix.get(2, 2), 0.0);
+    }
+
+    #[test]
+    fn distances_updated() {
+        let mut matrix = DistanceMatrix::new(3, vec![1.0, 2.0, 3.0]);
+        matrix.set(2, 1, 9.0);
+        assert_eq!(matrix.get(1, 2), 9.0);
+    }
+
+    #[test]
+    fn row_starts_correct() {
+        assert_eq!(DistanceMatrix::row_start(4, 0), 0);
+        assert_eq!(DistanceMatrix::row_start(4, 1), 3);
+        assert_eq!(DistanceMatrix::row_start(4, 2), 5);
+    }
+}
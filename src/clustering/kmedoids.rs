@@ -0,0 +1,345 @@
+use super::distance_matrix::DistanceMatrix;
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+
+// Smallest decrease in the finite part of the total cost, relative to it, for
+// which SWAP makes a swap that leaves as many inputs infinitely distant from
+// their medoid. Every swap strictly lowers the cost, so SWAP converges without
+// an iteration cap, and the tolerance stops it chasing rounding errors.
+const MIN_IMPROVEMENT: f64 = 1e-12;
+
+// Indices of k medoids of the inputs of `matrix`, in increasing order, found
+// by PAM: greedy BUILD followed by SWAP, evaluating the removal of every
+// medoid at once for each candidate as in FastPAM1
+pub fn pam(matrix: &DistanceMatrix, k: usize) -> Vec<usize> {
+    swap(matrix, build(matrix, k))
+}
+
+// Swap medoids for other inputs until no swap lowers the total cost
+fn swap(matrix: &DistanceMatrix, mut medoids: Vec<usize>) -> Vec<usize> {
+    let n = matrix.len();
+    let k = medoids.len();
+    let mut is_medoid = vec![false; n];
+    for &m in &medoids {
+        is_medoid[m] = true;
+    }
+
+    loop {
+        let nearest = Nearest::new(matrix, &medoids);
+        let total: Cost = nearest.first.iter().map(|&d| Cost::of(d)).sum();
+        let threshold = Cost {
+            infinite: 0,
+            finite: -MIN_IMPROVEMENT * total.finite,
+        };
+        let mut best: Option<(Cost, usize, usize)> = None;
+
+        for h in (0..n).filter(|&h| !is_medoid[h]) {
+            // Change in total cost of replacing each medoid with h
+            let mut shared = Cost::default();
+            let mut deltas = vec![Cost::default(); k];
+            for o in 0..n {
+                let d_oh = matrix.get(o, h);
+                let first = Cost::of(nearest.first[o]);
+                if d_oh < nearest.first[o] {
+                    shared = shared + (Cost::of(d_oh) - first);
+                } else {
+                    let slot = nearest.slot[o];
+                    deltas[slot] = deltas[slot] + (Cost::of(d_oh.min(nearest.second[o])) - first);
+                }
+            }
+
+            for (slot, delta) in deltas.into_iter().enumerate() {
+                let delta = shared + delta;
+                if delta < best.map_or(threshold, |(best, _, _)| best) {
+                    best = Some((delta, slot, h));
+                }
+            }
+        }
+
+        match best {
+            Some((_, slot, h)) => {
+                is_medoid[medoids[slot]] = false;
+                is_medoid[h] = true;
+                medoids[slot] = h;
+            }
+            None => break,
+        }
+    }
+
+    medoids.sort_unstable();
+    medoids
+}
+
+// Greedily add the medoid that most reduces the total cost
+fn build(matrix: &DistanceMatrix, k: usize) -> Vec<usize> {
+    let n = matrix.len();
+    let mut nearest = vec![f64::INFINITY; n];
+    let mut medoids = Vec::with_capacity(k);
+
+    for _ in 0..k {
+        let mut best: Option<(Cost, usize)> = None;
+        for c in (0..n).filter(|c| !medoids.contains(c)) {
+            let cost: Cost = (0..n)
+                .map(|o| Cost::of(nearest[o].min(matrix.get(o, c))))
+                .sum();
+            if best.is_none_or(|(best, _)| cost < best) {
+                best = Some((cost, c));
+            }
+        }
+
+        let (_, c) = best.expect("k no greater than the number of inputs");
+        for (o, d) in nearest.iter_mut().enumerate() {
+            *d = d.min(matrix.get(o, c));
+        }
+        medoids.push(c);
+    }
+    medoids
+}
+
+// Total of distances, counting infinite distances apart from the sum of the
+// finite ones, so that costs stay comparable when some inputs are infinitely
+// distant from every medoid. Costs order first by the number of infinite
+// distances.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cost {
+    infinite: isize,
+    finite: f64,
+}
+
+impl Cost {
+    pub fn of(distance: f64) -> Cost {
+        if distance.is_infinite() {
+            Cost {
+                infinite: 1,
+                finite: 0.0,
+            }
+        } else {
+            Cost {
+                infinite: 0,
+                finite: distance,
+            }
+        }
+    }
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, other: Cost) -> Cost {
+        Cost {
+            infinite: self.infinite + other.infinite,
+            finite: self.finite + other.finite,
+        }
+    }
+}
+
+impl Sub for Cost {
+    type Output = Cost;
+
+    fn sub(self, other: Cost) -> Cost {
+        Cost {
+            infinite: self.infinite - other.infinite,
+            finite: self.finite - other.finite,
+        }
+    }
+}
+
+impl Sum for Cost {
+    fn sum<I: Iterator<Item = Cost>>(iter: I) -> Cost {
+        iter.fold(Cost::default(), Add::add)
+    }
+}
+
+// Distances from every input to its nearest and second nearest medoids
+struct Nearest {
+    slot: Vec<usize>,
+    first: Vec<f64>,
+    second: Vec<f64>,
+}
+
+impl Nearest {
+    fn new(matrix: &DistanceMatrix, medoids: &[usize]) -> Nearest {
+        let n = matrix.len();
+        let mut nearest = Nearest {
+            slot: vec![0; n],
+            first: vec![f64::INFINITY; n],
+            second: vec![f64::INFINITY; n],
+        };
+
+        for o in 0..n {
+            for (slot, &m) in medoids.iter().enumerate() {
+                let d = matrix.get(o, m);
+                if d < nearest.first[o] {
+                    nearest.second[o] = nearest.first[o];
+                    nearest.first[o] = d;
+                    nearest.slot[o] = slot;
+                } else if d < nearest.second[o] {
+                    nearest.second[o] = d;
+                }
+            }
+        }
+        nearest
+    }
+}
+
+// Linear congruential generator, so that sampling is reproducible without
+// depending on a random number crate
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg(seed)
+    }
+
+    // Uniform value in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        // The high bits have the longest periods
+        ((self.0 >> 33) % n as u64) as usize
+    }
+}
+
+// `size` distinct indices in 0..n, in increasing order, including every index
+// of `include`
+pub fn sample(n: usize, size: usize, rng: &mut Lcg, include: &[usize]) -> Vec<usize> {
+    let mut pool: Vec<usize> = (0..n).filter(|i| !include.contains(i)).collect();
+    let mut sample = include.to_vec();
+
+    // Partial Fisher-Yates shuffle of the pool
+    for i in 0..size.saturating_sub(include.len()).min(pool.len()) {
+        let j = i + rng.below(pool.len() - i);
+        pool.swap(i, j);
+        sample.push(pool[i]);
+    }
+    sample.sort_unstable();
+    sample
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pam, sample, swap, DistanceMatrix, Lcg};
+
+    // Distances between points on a line
+    fn line(points: &[f64]) -> DistanceMatrix {
+        let mut distances = Vec::new();
+        for (i, a) in points.iter().enumerate() {
+            distances.extend(points[i + 1..].iter().map(|b| (a - b).abs()));
+        }
+        DistanceMatrix::new(points.len(), distances)
+    }
+
+    // Total distance of every input to its nearest medoid
+    fn cost(matrix: &DistanceMatrix, medoids: &[usize]) -> f64 {
+        (0..matrix.len())
+            .map(|o| {
+                medoids
+                    .iter()
+                    .map(|&m| matrix.get(o, m))
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .sum()
+    }
+
+    mod pam {
+        use super::{cost, line, pam, swap, DistanceMatrix, Lcg};
+
+        #[test]
+        fn medoids_central_to_groups() {
+            let matrix = line(&[0.0, 1.0, 2.0, 10.0, 11.0, 12.0]);
+            assert_eq!(pam(&matrix, 2), vec![1, 4]);
+            let matrix = line(&[0.0, 1.0, 2.0, 10.0, 11.0, 12.0, 30.0]);
+            assert_eq!(pam(&matrix, 3), vec![1, 4, 6]);
+        }
+
+        #[test]
+        fn swap_reaches_optimum() {
+            // BUILD first picks the overall median 6, which is no medoid of an
+            // optimal pair
+            let points = [0.0, 1.0, 2.0, 6.0, 10.0, 11.0, 13.0];
+            let matrix = line(&points);
+            let mut optimum = f64::INFINITY;
+            for a in 0..points.len() {
+                for b in a + 1..points.len() {
+                    optimum = optimum.min(cost(&matrix, &[a, b]));
+                }
+            }
+            let medoids = pam(&matrix, 2);
+            assert!(!medoids.contains(&3));
+            assert_eq!(cost(&matrix, &medoids), optimum);
+        }
+
+        #[test]
+        fn swap_runs_to_convergence() {
+            // Scattered points, so that BUILD leaves many swaps to be made
+            let mut rng = Lcg::new(3);
+            let points: Vec<f64> = (0..120).map(|_| rng.below(100_000) as f64).collect();
+            let matrix = line(&points);
+            let medoids = pam(&matrix, 15);
+            let total = cost(&matrix, &medoids);
+
+            // No single swap lowers the cost any further
+            for slot in 0..medoids.len() {
+                for h in (0..points.len()).filter(|h| !medoids.contains(h)) {
+                    let mut swapped = medoids.clone();
+                    swapped[slot] = h;
+                    assert!(cost(&matrix, &swapped) >= total * (1.0 - 1e-9));
+                }
+            }
+        }
+
+        #[test]
+        fn infinite_distances_reduced_first() {
+            // Two groups infinitely distant from each other, such as strings
+            // of two lengths under Hamming distance
+            let inf = f64::INFINITY;
+            let matrix =
+                DistanceMatrix::new(5, vec![1.0, 2.0, inf, inf, 1.0, inf, inf, inf, inf, 1.0]);
+            assert_eq!(pam(&matrix, 2), vec![1, 3]);
+            assert_eq!(swap(&matrix, vec![0, 1]), vec![1, 3]);
+        }
+
+        #[test]
+        fn swap_lowers_finite_cost_with_unreachable_inputs() {
+            // Input 3 is infinitely distant from every other input, so the
+            // total cost stays infinite, but the medoid still moves to 1
+            let inf = f64::INFINITY;
+            let matrix = DistanceMatrix::new(4, vec![1.0, 2.0, inf, 1.0, inf, inf]);
+            assert_eq!(swap(&matrix, vec![0]), vec![1]);
+            assert_eq!(pam(&matrix, 1), vec![1]);
+        }
+
+        #[test]
+        fn every_input_a_medoid() {
+            let matrix = line(&[0.0, 5.0, 9.0]);
+            assert_eq!(pam(&matrix, 3), vec![0, 1, 2]);
+        }
+    }
+
+    mod sample {
+        use super::{sample, Lcg};
+
+        #[test]
+        fn indices_distinct_and_sorted() {
+            let mut rng = Lcg::new(7);
+            let indices = sample(100, 20, &mut rng, &[3, 50]);
+            assert_eq!(indices.len(), 20);
+            assert!(indices.windows(2).all(|w| w[0] < w[1]));
+            assert!(indices.contains(&3) && indices.contains(&50));
+        }
+
+        #[test]
+        fn reproducible() {
+            let a = sample(100, 10, &mut Lcg::new(1), &[]);
+            let b = sample(100, 10, &mut Lcg::new(1), &[]);
+            assert_eq!(a, b);
+        }
+
+        #[test]
+        fn capped_at_input_count() {
+            assert_eq!(sample(3, 10, &mut Lcg::new(1), &[1]), vec![0, 1, 2]);
+        }
+    }
+}
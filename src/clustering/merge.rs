@@ -1,21 +1,25 @@
-use super::is_similar;
+use super::Metric;
 
-pub fn merge_clusters<'a>(
-    set_one: &mut Vec<Vec<&'a str>>,
-    set_two: &mut Vec<Vec<&'a str>>,
-    max_edit_frac: f32,
-) -> Vec<Vec<&'a str>> {
+pub fn merge_clusters<'a, S, M>(
+    set_one: &mut Vec<Vec<&'a S>>,
+    set_two: &mut [Vec<&'a S>],
+    metric: &M,
+) -> Vec<Vec<&'a S>>
+where
+    S: ?Sized,
+    M: Metric<S>,
+{
     // Store if values have already been merged
     let mut moved = vec![false; set_two.len()];
 
-    for i in 0..set_one.len() {
+    for cluster in set_one.iter_mut() {
         for j in 0..set_two.len() {
             if moved[j] {
                 continue;
             }
 
-            if is_similar(set_one[i][0], set_two[j][0], max_edit_frac) {
-                set_one[i].append(&mut set_two[j]);
+            if metric.is_similar(cluster[0], set_two[j][0]) {
+                cluster.append(&mut set_two[j]);
                 moved[j] = true;
             }
         }
@@ -36,13 +40,15 @@ mod tests {
 
     mod merge_clusters {
         use super::merge_clusters;
+        use crate::metric::Levenshtein;
 
         #[test]
         fn test_merge_no_overlap() {
             let mut set_one = vec![vec!["a"], vec!["b"]];
             let mut set_two = vec![vec!["c"], vec!["d"]];
             let expected = vec![vec!["a"], vec!["b"], vec!["c"], vec!["d"]];
-            let result = merge_clusters(&mut set_one, &mut set_two, 0.0);
+            let result =
+                merge_clusters(&mut set_one, &mut set_two, &Levenshtein::new(0.0).unwrap());
             assert_eq!(result, expected);
         }
 
@@ -51,7 +57,8 @@ mod tests {
             let mut set_one = vec![vec!["aa"], vec!["bb"]];
             let mut set_two = vec![vec!["aa"], vec!["bb"]];
             let expected = vec![vec!["aa", "aa"], vec!["bb", "bb"]];
-            let result = merge_clusters(&mut set_one, &mut set_two, 0.5);
+            let result =
+                merge_clusters(&mut set_one, &mut set_two, &Levenshtein::new(0.5).unwrap());
             assert_eq!(result, expected);
         }
 
@@ -60,7 +67,8 @@ mod tests {
             let mut set_one = vec![vec!["aa"], vec!["bb"]];
             let mut set_two = vec![vec!["aa"], vec!["bb"], vec!["cc"]];
             let expected = vec![vec!["aa", "aa"], vec!["bb", "bb"], vec!["cc"]];
-            let result = merge_clusters(&mut set_one, &mut set_two, 0.5);
+            let result =
+                merge_clusters(&mut set_one, &mut set_two, &Levenshtein::new(0.5).unwrap());
             assert_eq!(result, expected);
         }
 
@@ -69,7 +77,8 @@ mod tests {
             let mut set_one = vec![vec!["aa"], vec!["cc"]];
             let mut set_two = vec![vec!["ab"], vec!["cd"]];
             let expected = vec![vec!["aa", "ab"], vec!["cc", "cd"]];
-            let result = merge_clusters(&mut set_one, &mut set_two, 0.5);
+            let result =
+                merge_clusters(&mut set_one, &mut set_two, &Levenshtein::new(0.5).unwrap());
             assert_eq!(result, expected);
         }
     }
@@ -0,0 +1,18 @@
+//! # Density-Based Clustering
+//! DBSCAN groups inputs lying in dense regions of the similarity graph and
+//! labels inputs in sparse regions as noise, rather than returning each of them
+//! as a singleton cluster.
+//!
+//! An input is a core input if at least `min_samples` inputs, counting itself,
+//! are similar to it. Clusters are the connected groups of similar core inputs,
+//! and each remaining input joins the cluster of its first similar core input.
+//! Inputs similar to no core input are noise.
+
+/// Clusters and noise found by density-based clustering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DensityClusters<'a, S: ?Sized = str> {
+    /// Clusters of inputs in dense regions, in input order.
+    pub clusters: Vec<Vec<&'a S>>,
+    /// Inputs in no cluster, in input order.
+    pub noise: Vec<&'a S>,
+}
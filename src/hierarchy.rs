@@ -0,0 +1,289 @@
+//! # Hierarchical Clustering
+//! Agglomerative clustering starts with every input in its own cluster and
+//! repeatedly merges the two closest clusters, as measured by
+//! [`Metric::distance`] and a [`Linkage`] rule, until the hierarchy is cut by
+//! a [`Cut`].
+//!
+//! Unlike leader clustering, every pair of inputs is compared, so clusters do
+//! not depend on input order or thread count. The pairwise distances are held
+//! in memory, which needs O(n^2) space.
+//!
+//! A [`Dendrogram`] records the full merge hierarchy, so it can be built once
+//! and cut at many thresholds without measuring any distances again.
+
+use crate::clustering::agglomerative::{cut_merges, Merge};
+use crate::clustering::disjoint_set::DisjointSet;
+use crate::metric::{Levenshtein, Metric};
+use crate::ValueError;
+
+/// Rule by which the distance between two clusters is derived from the
+/// distances between their members.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Linkage {
+    /// Distance between the closest pair of members. Prone to chaining.
+    Single,
+    /// Distance between the farthest pair of members, so every pair of members
+    /// of a cluster is within the cut distance.
+    #[default]
+    Complete,
+    /// Mean distance between all pairs of members (UPGMA).
+    Average,
+    /// Increase in within-cluster variance caused by the merge, treating
+    /// distances as Euclidean. Favours compact clusters of similar size.
+    Ward,
+}
+
+/// Point at which merging of clusters stops.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cut {
+    /// Merge clusters while their linkage distance is at most this value.
+    Distance(f64),
+    /// Merge clusters until this many remain.
+    Clusters(usize),
+}
+
+impl Cut {
+    pub(crate) fn validate(&self, n: usize) -> Result<(), ValueError> {
+        match *self {
+            Cut::Distance(max) if max.is_nan() || max < 0.0 => Err(ValueError::InvalidCutDistance),
+            Cut::Clusters(k) if k == 0 || k > n => Err(ValueError::InvalidClusterCount),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Merge of two clusters in a [`Dendrogram`].
+///
+/// Clusters are identified as in a scipy linkage matrix: ids below the number
+/// of inputs n are the singleton clusters of each input, and id n + i is the
+/// cluster formed by the i-th step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Step {
+    /// Smaller id of the merged clusters.
+    pub left: usize,
+    /// Larger id of the merged clusters.
+    pub right: usize,
+    /// Linkage distance between the merged clusters.
+    pub distance: f64,
+    /// Number of inputs in the merged cluster.
+    pub size: usize,
+}
+
+/// Full merge hierarchy of agglomerative clustering over a set of inputs.
+///
+/// The n - 1 steps are in order of increasing distance. Cutting the
+/// dendrogram only replays its steps, so each cut takes near-linear time.
+#[derive(Clone, Debug)]
+pub struct Dendrogram<'a, S: ?Sized = str> {
+    inputs: &'a [&'a S],
+    merges: Vec<Merge>,
+    steps: Vec<Step>,
+}
+
+impl<'a, S: ?Sized> Dendrogram<'a, S> {
+    // `merges` must be sorted by distance, with each merge naming one input
+    // from either cluster
+    pub(crate) fn new(inputs: &'a [&'a S], merges: Vec<Merge>) -> Dendrogram<'a, S> {
+        let n = inputs.len();
+        let mut forest = DisjointSet::new(n);
+        // Cluster id and size of each tree, indexed by its root
+        let mut ids: Vec<usize> = (0..n).collect();
+        let mut sizes = vec![1; n];
+
+        let steps = merges
+            .iter()
+            .enumerate()
+            .map(|(i, merge)| {
+                let (a, b) = (forest.find(merge.a), forest.find(merge.b));
+                let (left, right) = (ids[a].min(ids[b]), ids[a].max(ids[b]));
+                let size = sizes[a] + sizes[b];
+                forest.union(a, b);
+                let root = forest.find(a);
+                ids[root] = n + i;
+                sizes[root] = size;
+                Step {
+                    left,
+                    right,
+                    distance: merge.distance,
+                    size,
+                }
+            })
+            .collect();
+
+        Dendrogram {
+            inputs,
+            merges,
+            steps,
+        }
+    }
+
+    /// Inputs at the leaves of the dendrogram, indexed by cluster id.
+    pub fn inputs(&self) -> &'a [&'a S] {
+        self.inputs
+    }
+
+    /// Merge steps in order of increasing distance.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Merge steps as rows of a scipy linkage matrix, `[left, right, distance, size]`.
+    ///
+    /// The rows can be passed to `scipy.cluster.hierarchy` functions such as
+    /// `dendrogram` and `fcluster`. scipy rejects infinite distances, which
+    /// custom metrics may report between inputs they never consider similar,
+    /// and which are exported as they are.
+    pub fn linkage_matrix(&self) -> Vec<[f64; 4]> {
+        self.steps
+            .iter()
+            .map(|step| {
+                [
+                    step.left as f64,
+                    step.right as f64,
+                    step.distance,
+                    step.size as f64,
+                ]
+            })
+            .collect()
+    }
+
+    /// Clusters obtained by cutting the dendrogram.
+    ///
+    /// Clusters and their members are in input order.
+    ///
+    /// # Errors
+    /// [`ValueError::InvalidClusterCount`] if a target cluster count is zero or
+    /// greater than the number of inputs.
+    ///
+    /// [`ValueError::InvalidCutDistance`] if a cut distance is negative or NaN.
+    pub fn cut(&self, cut: Cut) -> Result<Vec<Vec<&'a S>>, ValueError> {
+        cut.validate(self.inputs.len())?;
+
+        let clusters = cut_merges(self.inputs.len(), &self.merges, cut)
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|i| self.inputs[i]).collect())
+            .collect();
+        Ok(clusters)
+    }
+}
+
+// Levenshtein distance as a fraction of the shorter string's length, so that
+// strings within a distance of `max_edit_frac` are exactly those that
+// `Levenshtein` considers similar. No edit is allowed against an empty string,
+// so its distance to any other string is one more than the edit count: above
+// every valid fraction, but finite as scipy requires of linkage heights.
+pub(crate) struct EditFraction(pub Levenshtein);
+
+impl Metric for EditFraction {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.0.is_similar(a, b)
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        let edits = self.0.distance(a, b);
+        if edits == 0.0 {
+            return 0.0;
+        }
+        match a.chars().count().min(b.chars().count()) {
+            0 => 1.0 + edits,
+            shorter => edits / shorter as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cut, Dendrogram, EditFraction, Merge, Step};
+    use crate::metric::{Levenshtein, Metric};
+    use crate::ValueError;
+
+    // Points 0, 1, 3 and 10 on a line under single linkage
+    fn line() -> Dendrogram<'static> {
+        static INPUTS: [&str; 4] = ["0", "1", "3", "10"];
+        let merge = |a, b, distance| Merge { a, b, distance };
+        Dendrogram::new(
+            &INPUTS,
+            vec![merge(0, 1, 1.0), merge(1, 2, 2.0), merge(2, 3, 7.0)],
+        )
+    }
+
+    #[test]
+    fn steps_numbered_as_scipy() {
+        let dendrogram = line();
+        let step = |left, right, distance, size| Step {
+            left,
+            right,
+            distance,
+            size,
+        };
+        let expected = vec![step(0, 1, 1.0, 2), step(2, 4, 2.0, 3), step(3, 5, 7.0, 4)];
+        assert_eq!(dendrogram.steps(), expected);
+        assert_eq!(
+            dendrogram.linkage_matrix(),
+            vec![
+                [0.0, 1.0, 1.0, 2.0],
+                [2.0, 4.0, 2.0, 3.0],
+                [3.0, 5.0, 7.0, 4.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn cut_at_any_threshold() {
+        let dendrogram = line();
+        assert_eq!(dendrogram.cut(Cut::Distance(0.5)).unwrap().len(), 4);
+        assert_eq!(
+            dendrogram.cut(Cut::Distance(2.0)).unwrap(),
+            vec![vec!["0", "1", "3"], vec!["10"]]
+        );
+        assert_eq!(
+            dendrogram.cut(Cut::Clusters(1)).unwrap(),
+            vec![vec!["0", "1", "3", "10"]]
+        );
+    }
+
+    #[test]
+    fn cluster_count_validated() {
+        let dendrogram = line();
+        assert_eq!(
+            dendrogram.cut(Cut::Clusters(0)),
+            Err(ValueError::InvalidClusterCount)
+        );
+        assert_eq!(
+            dendrogram.cut(Cut::Clusters(5)),
+            Err(ValueError::InvalidClusterCount)
+        );
+    }
+
+    #[test]
+    fn cut_distance_validated() {
+        let dendrogram = line();
+        for max in [f64::NAN, -0.5] {
+            assert_eq!(
+                dendrogram.cut(Cut::Distance(max)),
+                Err(ValueError::InvalidCutDistance)
+            );
+        }
+        assert_eq!(
+            dendrogram.cut(Cut::Distance(f64::INFINITY)).unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn edits_divided_by_shorter_length() {
+        let metric = EditFraction(Levenshtein::new(0.25).unwrap());
+        assert_eq!(metric.distance("aaaa", "aaab"), 0.25);
+        assert_eq!(metric.distance("aa", "aaaa"), 1.0);
+        assert_eq!(metric.distance("abc", "abc"), 0.0);
+    }
+
+    #[test]
+    fn empty_strings_handled() {
+        let metric = EditFraction(Levenshtein::new(0.25).unwrap());
+        assert_eq!(metric.distance("", ""), 0.0);
+        assert_eq!(metric.distance("", "a"), 2.0);
+        assert_eq!(metric.distance("abc", ""), 4.0);
+    }
+}
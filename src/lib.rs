@@ -9,12 +9,12 @@
 //!
 //! # Multithreading model
 //! * The input strings are evenly paritioned across the set of allocated
-//! threads.
+//!   threads.
 //! * Once each thread has clustered its associated input strings, result aggregation is
-//! started.
+//!   started.
 //! * Clusters are merged in pairs accross multiple threads in a manner that is similar to
-//! traversing a binary tree from the leaves up to the root. The root of the tree is the final
-//! clustering.
+//!   traversing a binary tree from the leaves up to the root. The root of the tree is the final
+//!   clustering.
 //! * Thus, if there are N threads allocated, there will be ceil(log2(N)) merge operations.
 //!
 //! # Optimisation
@@ -29,6 +29,43 @@
 //! As a result, if this property cannot be exploited on the desired input data, another implementation
 //! should be used.
 //!
+//! Strings are only compared with cluster representatives, so a chain of similar strings may still
+//! be split across clusters. [`Mode::SingleLinkage`], selected with [`cluster_strings_with_mode`],
+//! guarantees transitivity by forming the connected components of the similarity graph, at the cost
+//! of comparing more pairs of strings.
+//!
+//! # Metrics
+//! The similarity rule is pluggable. Any type implementing [`metric::Metric`] can be
+//! passed to [`cluster_strings_with_metric`]. The Levenshtein rule described above is
+//! provided as [`metric::Levenshtein`].
+//!
+//! # Distances
+//! [`cluster_strings_with_distances`] additionally reports the distance of every
+//! member to its cluster's representative, as measured by [`metric::Metric::distance`],
+//! so that loose clusters and borderline members can be identified.
+//!
+//! # Hierarchical clustering
+//! [`cluster_strings_hierarchical`] is an alternative to leader clustering that
+//! merges the closest clusters under complete, average or Ward linkage, avoiding
+//! chained, heterogeneous clusters at the cost of comparing every pair of strings.
+//! [`cluster_strings_dendrogram`] returns the full merge hierarchy as a
+//! [`hierarchy::Dendrogram`], which can be cut at many thresholds without measuring
+//! distances again. See [`hierarchy`] for details.
+//!
+//! # Noise
+//! [`cluster_strings_dbscan`] clusters strings by density, returning one-off strings
+//! similar to too few others as an explicit noise set instead of as singleton
+//! clusters. See [`density`] for details.
+//!
+//! # Fixed number of clusters
+//! [`cluster_strings_kmedoids`] partitions strings into exactly k clusters around
+//! medoid strings, when the number of groups is known in advance. See [`medoids`]
+//! for details.
+//!
+//! # Sequences
+//! Sequences other than strings, such as raw bytes or token ids, can be clustered
+//! with [`cluster_sequences`] and [`cluster_sequences_with_metric`].
+//!
 //! # Installation
 //!
 //! Add this to your Cargo.toml
@@ -75,11 +112,25 @@
 //! ```
 
 mod clustering;
-mod metric;
+pub mod density;
+pub mod hierarchy;
+pub mod medoids;
+pub mod metric;
 mod threading;
 
+use density::DensityClusters;
+use hierarchy::{Cut, Dendrogram, EditFraction, Linkage};
+use medoids::{k_medoids, Medoids};
+use metric::{Levenshtein, Metric};
+
+use clustering::agglomerative::agglomerate;
+use clustering::dbscan::dbscan;
 use threading::aggregation::aggregate_results;
+use threading::components::connected_components;
+use threading::distances::pairwise_distances;
 use threading::formation::form_clusters;
+use threading::neighbourhoods::find_neighbours;
+use threading::scoring::score_clusters;
 
 /// Validation errors. Errors associated with invalid function argument values.
 #[derive(PartialEq, Debug)]
@@ -92,6 +143,48 @@ pub enum ValueError {
     InsufficientWork,
     /// Thread count less than one.
     InsufficientThreadCount,
+    /// Jaro-Winkler prefix scale outside of closed interval \[0,0.25\].
+    InvalidPrefixScale,
+    /// Q-gram size less than one.
+    InvalidGramSize,
+    /// Minimum edit count greater than maximum edit count.
+    InvalidEditBounds,
+    /// Edit cost negative or not finite.
+    InvalidCost,
+    /// Regular expression failed to compile.
+    InvalidPattern,
+    /// Input strings of different lengths compared by a metric requiring equal lengths.
+    UnequalLengths,
+    /// Field weight not positive and finite.
+    InvalidWeight,
+    /// Record with a different number of fields than the metric compares.
+    FieldCountMismatch,
+    /// Alignment score not finite, or score of a matching pair not positive.
+    InvalidScore,
+    /// Target cluster count zero or greater than the number of inputs.
+    InvalidClusterCount,
+    /// Minimum sample count less than one.
+    InvalidSampleCount,
+    /// Field delimiter empty.
+    InvalidDelimiter,
+    /// Cut distance negative or not a number.
+    InvalidCutDistance,
+}
+
+/// Rule by which similar inputs are grouped into clusters.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Each input joins the cluster of the first representative it is similar
+    /// to, and clusters formed by different threads are merged by comparing
+    /// their representatives. Fast, but chains of similar inputs may be split.
+    #[default]
+    Leader,
+    /// Exact single linkage: clusters are the connected components of the graph
+    /// in which similar inputs are adjacent, so if A~B and B~C then A, B and C
+    /// are clustered together regardless of the thread count. Pairs already
+    /// known to be connected are skipped, but up to every pair of inputs is
+    /// compared. Clusters and their members are in input order.
+    SingleLinkage,
 }
 
 /// Group similar input strings into clusters.
@@ -99,6 +192,10 @@ pub enum ValueError {
 /// Strings will be grouped into a cluster if the Levenshtein distance between the
 /// strings is below 'max_edit_frac' of the shorter string's length.
 ///
+/// Other thresholds, such as a fraction of the longer string's length or an absolute
+/// number of edits, can be selected by passing a [`metric::Levenshtein`] configured
+/// with a [`metric::EditThreshold`] to [`cluster_strings_with_metric`].
+///
 /// # Examples
 /// Basic usage:
 /// ```
@@ -140,20 +237,511 @@ pub fn cluster_strings<'a>(
     n_threads: usize,
 ) -> Result<Vec<Vec<&'a str>>, ValueError> {
     // Validation here to avoid having to propagate errors out of threads
-    if inputs.len() == 0 {
+    if inputs.is_empty() {
+        return Err(ValueError::EmptyVector);
+    }
+    let metric = Levenshtein::new(max_edit_frac)?;
+    cluster_strings_with_metric(inputs, metric, n_threads)
+}
+
+/// Group similar input strings into clusters using a custom similarity metric.
+///
+/// Strings will be grouped into a cluster if `metric` considers them similar.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Levenshtein;
+///
+/// let inputs = vec!["aaaa", "aaax", "bbbb", "bbbz"];
+/// let expected = vec![vec!["aaaa", "aaax"], vec!["bbbb", "bbbz"]];
+///
+/// let metric = Levenshtein::new(0.25)?;
+/// let clusters = clustr::cluster_strings_with_metric(&inputs, metric, 1)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_with_metric<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    metric: M,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a str>>, ValueError> {
+    cluster_strings_with_mode(inputs, metric, Mode::Leader, n_threads)
+}
+
+/// Group similar input strings into clusters using a custom similarity metric
+/// and clustering mode.
+///
+/// # Examples
+/// Chains of similar strings clustered together:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Levenshtein;
+/// use clustr::Mode;
+///
+/// let inputs = vec!["aaaa", "bbbb", "aabb", "abbb", "aaab"];
+/// let expected = vec![inputs.clone()];
+///
+/// let metric = Levenshtein::new(0.25)?;
+/// let clusters = clustr::cluster_strings_with_mode(&inputs, metric, Mode::SingleLinkage, 2)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_with_mode<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    mode: Mode,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a str>>, ValueError> {
+    cluster_with_metric(inputs, &mut metric, mode, n_threads)
+}
+
+/// Group similar input strings into clusters and report each member's distance
+/// to its cluster's representative.
+///
+/// Clusters are formed as by [`cluster_strings_with_metric`]. The first member
+/// of each cluster is its representative, and every member is paired with
+/// [`Metric::distance`] between the representative and the member.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Levenshtein;
+///
+/// let inputs = vec!["aaaa", "aaax", "bbbb", "bbbz"];
+/// let expected = vec![
+///     vec![("aaaa", 0.0), ("aaax", 1.0)],
+///     vec![("bbbb", 0.0), ("bbbz", 1.0)],
+/// ];
+///
+/// let metric = Levenshtein::new(0.25)?;
+/// let clusters = clustr::cluster_strings_with_distances(&inputs, metric, 1)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_with_distances<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    n_threads: usize,
+) -> Result<Vec<Vec<(&'a str, f64)>>, ValueError> {
+    let clusters = cluster_with_metric(inputs, &mut metric, Mode::Leader, n_threads)?;
+    Ok(score_clusters(&clusters, &metric, n_threads))
+}
+
+/// Group similar input strings into clusters by agglomerative hierarchical
+/// clustering.
+///
+/// Clusters are merged while the `linkage` distance between them is at most
+/// 'max_edit_frac', where the distance between two strings is their Levenshtein
+/// distance as a fraction of the shorter string's length. Under
+/// [`Linkage::Complete`], every pair of strings in a cluster is therefore similar
+/// by the rule of [`cluster_strings`].
+///
+/// Clusters and their members are in input order, regardless of the thread count.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::hierarchy::Linkage;
+///
+/// let inputs = vec!["abcd", "abce", "wxyz", "abcf", "vxyz"];
+/// let expected = vec![vec!["abcd", "abce", "abcf"], vec!["wxyz", "vxyz"]];
+///
+/// let clusters = clustr::cluster_strings_hierarchical(&inputs, 0.25, Linkage::Complete, 2)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_hierarchical<'a>(
+    inputs: &'a Vec<&'a str>,
+    max_edit_frac: f32,
+    linkage: Linkage,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a str>>, ValueError> {
+    let metric = EditFraction(Levenshtein::new(max_edit_frac)?);
+    let cut = Cut::Distance(max_edit_frac as f64);
+    cluster_strings_hierarchical_with_metric(inputs, metric, linkage, cut, n_threads)
+}
+
+/// Group input strings into clusters by agglomerative hierarchical clustering
+/// using a custom metric.
+///
+/// Distances between strings are measured by [`Metric::distance`], and clusters
+/// are merged in order of increasing `linkage` distance until `cut` is reached.
+///
+/// # Examples
+/// Fixed number of clusters:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::hierarchy::{Cut, Linkage};
+/// use clustr::metric::Levenshtein;
+///
+/// let inputs = vec!["aaaa", "bbbb", "aaab", "bbba", "aabb"];
+/// let expected = vec![vec!["aaaa", "aaab", "aabb"], vec!["bbbb", "bbba"]];
+///
+/// let metric = Levenshtein::new(0.0)?;
+/// let clusters = clustr::cluster_strings_hierarchical_with_metric(
+///     &inputs,
+///     metric,
+///     Linkage::Average,
+///     Cut::Clusters(2),
+///     1,
+/// )?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_hierarchical_with_metric<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    linkage: Linkage,
+    cut: Cut,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a str>>, ValueError> {
+    prepare(inputs, &mut metric, n_threads)?;
+    // Validated before the pairwise distances are measured
+    cut.validate(inputs.len())?;
+    let distances = pairwise_distances(inputs, &metric, n_threads);
+    Dendrogram::new(inputs, agglomerate(distances, linkage)).cut(cut)
+}
+
+/// Build the full merge hierarchy of agglomerative clustering over the input
+/// strings.
+///
+/// Distances are measured as by [`cluster_strings_hierarchical`], so cutting the
+/// dendrogram at [`Cut::Distance`] of 'max_edit_frac' gives the same clusters as
+/// calling it with that fraction. Pairwise distances are measured once, and every
+/// later cut reuses them.
+///
+/// # Examples
+/// Clusters at several thresholds:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::hierarchy::{Cut, Linkage};
+///
+/// let inputs = vec!["aaaa", "aaab", "bbbb", "aabb"];
+///
+/// let dendrogram = clustr::cluster_strings_dendrogram(&inputs, Linkage::Single, 1)?;
+///
+/// assert_eq!(dendrogram.linkage_matrix()[0], [0.0, 1.0, 0.25, 2.0]);
+/// assert_eq!(dendrogram.cut(Cut::Distance(0.0))?.len(), 4);
+/// assert_eq!(
+///     dendrogram.cut(Cut::Distance(0.25))?,
+///     vec![vec!["aaaa", "aaab", "aabb"], vec!["bbbb"]]
+/// );
+/// assert_eq!(dendrogram.cut(Cut::Distance(0.5))?, vec![inputs.clone()]);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_dendrogram<'a>(
+    inputs: &'a Vec<&'a str>,
+    linkage: Linkage,
+    n_threads: usize,
+) -> Result<Dendrogram<'a>, ValueError> {
+    // The fraction only affects similarity, not the distances measured
+    let metric = EditFraction(Levenshtein::new(0.0)?);
+    cluster_strings_dendrogram_with_metric(inputs, metric, linkage, n_threads)
+}
+
+/// Build the full merge hierarchy of agglomerative clustering over the input
+/// strings using a custom metric.
+///
+/// Distances between strings are measured by [`Metric::distance`].
+pub fn cluster_strings_dendrogram_with_metric<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    linkage: Linkage,
+    n_threads: usize,
+) -> Result<Dendrogram<'a>, ValueError> {
+    prepare(inputs, &mut metric, n_threads)?;
+    let distances = pairwise_distances(inputs, &metric, n_threads);
+    Ok(Dendrogram::new(inputs, agglomerate(distances, linkage)))
+}
+
+/// Group input strings into clusters by density, separating out noise.
+///
+/// Strings are similar if the Levenshtein distance between them is below
+/// 'max_edit_frac' of the shorter string's length. A string similar to at least
+/// 'min_samples' strings, counting itself, is a core string; see [`density`] for
+/// how clusters are formed from core strings.
+///
+/// Inputs are dealt out to threads in turn, and each thread compares its
+/// inputs with every later input. Clusters, their members and the noise are in
+/// input order, regardless of the thread count.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// let inputs = vec!["aaaa", "aaab", "xyz", "aaba", "bbbb", "bbbz", "bbzb", "qrst"];
+///
+/// let result = clustr::cluster_strings_dbscan(&inputs, 0.25, 3, 2)?;
+///
+/// assert_eq!(
+///     result.clusters,
+///     vec![vec!["aaaa", "aaab", "aaba"], vec!["bbbb", "bbbz", "bbzb"]]
+/// );
+/// assert_eq!(result.noise, vec!["xyz", "qrst"]);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_dbscan<'a>(
+    inputs: &'a Vec<&'a str>,
+    max_edit_frac: f32,
+    min_samples: usize,
+    n_threads: usize,
+) -> Result<DensityClusters<'a>, ValueError> {
+    let metric = Levenshtein::new(max_edit_frac)?;
+    cluster_strings_dbscan_with_metric(inputs, metric, min_samples, n_threads)
+}
+
+/// Group input strings into clusters by density using a custom similarity
+/// metric, separating out noise.
+///
+/// Strings are similar if `metric` considers them similar.
+pub fn cluster_strings_dbscan_with_metric<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    min_samples: usize,
+    n_threads: usize,
+) -> Result<DensityClusters<'a>, ValueError> {
+    prepare(inputs, &mut metric, n_threads)?;
+    if min_samples == 0 {
+        return Err(ValueError::InvalidSampleCount);
+    }
+
+    let neighbours = find_neighbours(inputs, &metric, n_threads);
+    let (clusters, noise) = dbscan(&neighbours, min_samples);
+    Ok(DensityClusters {
+        clusters: clusters
+            .into_iter()
+            .map(|cluster| cluster.into_iter().map(|i| inputs[i]).collect())
+            .collect(),
+        noise: noise.into_iter().map(|i| inputs[i]).collect(),
+    })
+}
+
+/// Partition input strings into exactly `k` clusters by k-medoids clustering.
+///
+/// The distance between two strings is their Levenshtein distance, and the
+/// medoids are chosen to minimise the total distance of every string to its
+/// medoid. See [`medoids`] for the algorithms used.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// let inputs = vec!["shirt", "shirts", "shoes", "shoe", "skirt", "shoes!"];
+///
+/// let result = clustr::cluster_strings_kmedoids(&inputs, 2, 2)?;
+///
+/// assert_eq!(result.medoids(), vec!["shirt", "shoes"]);
+/// assert_eq!(result.assignments(), vec![0, 0, 1, 1, 0, 1]);
+/// assert_eq!(
+///     result.clusters(),
+///     vec![vec!["shirt", "shirts", "skirt"], vec!["shoes", "shoe", "shoes!"]]
+/// );
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_strings_kmedoids<'a>(
+    inputs: &'a Vec<&'a str>,
+    k: usize,
+    n_threads: usize,
+) -> Result<Medoids<'a>, ValueError> {
+    // The fraction only affects similarity, not the distances measured
+    let metric = Levenshtein::new(0.0)?;
+    cluster_strings_kmedoids_with_metric(inputs, metric, k, n_threads)
+}
+
+/// Partition input strings into exactly `k` clusters by k-medoids clustering
+/// using a custom metric.
+///
+/// Distances between strings are measured by [`Metric::distance`].
+pub fn cluster_strings_kmedoids_with_metric<'a, M: Metric>(
+    inputs: &'a Vec<&'a str>,
+    mut metric: M,
+    k: usize,
+    n_threads: usize,
+) -> Result<Medoids<'a>, ValueError> {
+    prepare(inputs, &mut metric, n_threads)?;
+    if k == 0 || k > inputs.len() {
+        return Err(ValueError::InvalidClusterCount);
+    }
+    Ok(k_medoids(inputs, &metric, k, n_threads))
+}
+
+/// Group similar input sequences into clusters.
+///
+/// Sequences such as byte strings or token ids will be grouped into a cluster
+/// if the Levenshtein distance between the sequences is below 'max_edit_frac'
+/// of the shorter sequence's length.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// let inputs: Vec<&[u32]> = vec![&[1, 2, 3, 4], &[1, 2, 3, 5], &[7, 8, 9, 9]];
+/// let expected: Vec<Vec<&[u32]>> = vec![vec![&[1, 2, 3, 4], &[1, 2, 3, 5]], vec![&[7, 8, 9, 9]]];
+///
+/// let clusters = clustr::cluster_sequences(&inputs, 0.25, 1)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_sequences<'a, T: Eq + Sync>(
+    inputs: &'a Vec<&'a [T]>,
+    max_edit_frac: f32,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a [T]>>, ValueError> {
+    // Validation here to avoid having to propagate errors out of threads
+    if inputs.is_empty() {
         return Err(ValueError::EmptyVector);
     }
-    if max_edit_frac < 0.0 || max_edit_frac > 1.0 {
-        return Err(ValueError::InvalidFraction);
+    let metric = Levenshtein::new(max_edit_frac)?;
+    cluster_sequences_with_metric(inputs, metric, n_threads)
+}
+
+/// Group similar input sequences into clusters using a custom similarity metric.
+///
+/// Sequences will be grouped into a cluster if `metric` considers them similar.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Hamming;
+///
+/// let inputs: Vec<&[u8]> = vec![b"ACGTACGT", b"ACGTACGA", b"TTTTGGGG"];
+/// let expected: Vec<Vec<&[u8]>> = vec![vec![b"ACGTACGT", b"ACGTACGA"], vec![b"TTTTGGGG"]];
+///
+/// let metric = Hamming::new(0.125)?;
+/// let clusters = clustr::cluster_sequences_with_metric(&inputs, metric, 1)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_sequences_with_metric<'a, T: Sync, M: Metric<[T]>>(
+    inputs: &'a Vec<&'a [T]>,
+    metric: M,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a [T]>>, ValueError> {
+    cluster_sequences_with_mode(inputs, metric, Mode::Leader, n_threads)
+}
+
+/// Group similar input sequences into clusters using a custom similarity
+/// metric and clustering mode.
+///
+/// # Examples
+/// Basic usage:
+/// ```
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Levenshtein;
+/// use clustr::Mode;
+///
+/// let inputs: Vec<&[u32]> = vec![&[1, 1, 1], &[1, 1, 2], &[7, 8, 9], &[1, 2, 2]];
+/// let expected: Vec<Vec<&[u32]>> = vec![vec![&[1, 1, 1], &[1, 1, 2], &[1, 2, 2]], vec![&[7, 8, 9]]];
+///
+/// let metric = Levenshtein::new(0.34)?;
+/// let clusters = clustr::cluster_sequences_with_mode(&inputs, metric, Mode::SingleLinkage, 1)?;
+///
+/// assert_eq!(clusters, expected);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub fn cluster_sequences_with_mode<'a, T: Sync, M: Metric<[T]>>(
+    inputs: &'a Vec<&'a [T]>,
+    mut metric: M,
+    mode: Mode,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a [T]>>, ValueError> {
+    cluster_with_metric(inputs, &mut metric, mode, n_threads)
+}
+
+fn cluster_with_metric<'a, S, M>(
+    inputs: &'a [&'a S],
+    metric: &mut M,
+    mode: Mode,
+    n_threads: usize,
+) -> Result<Vec<Vec<&'a S>>, ValueError>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    prepare(inputs, metric, n_threads)?;
+
+    let result = match mode {
+        Mode::Leader => {
+            let clusters = form_clusters(inputs, metric, n_threads);
+            aggregate_results(clusters, metric)
+        }
+        Mode::SingleLinkage => connected_components(inputs, metric, n_threads),
+    };
+    Ok(result)
+}
+
+// Validation here to avoid having to propagate errors out of threads
+fn prepare<S, M>(inputs: &[&S], metric: &mut M, n_threads: usize) -> Result<(), ValueError>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    if inputs.is_empty() {
+        return Err(ValueError::EmptyVector);
     }
     if n_threads > inputs.len() {
         return Err(ValueError::InsufficientWork);
     }
-    if n_threads <= 0 {
+    if n_threads == 0 {
         return Err(ValueError::InsufficientThreadCount);
     }
-
-    let clusters = form_clusters(inputs, max_edit_frac, n_threads);
-    let result = aggregate_results(clusters, max_edit_frac);
-    Ok(result)
+    metric.validate(inputs)?;
+    metric.fit(inputs);
+    Ok(())
 }
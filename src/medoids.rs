@@ -0,0 +1,158 @@
+//! # k-Medoids Clustering
+//! k-medoids partitions the inputs into exactly k clusters, each represented by
+//! one of its members, the medoid, so as to minimise the total
+//! [`Metric::distance`] of every input to its medoid.
+//!
+//! Up to 1000 inputs are clustered by PAM over the full pairwise distance
+//! matrix. Larger inputs are clustered by CLARA, which runs PAM on five samples
+//! of 40 + 2k inputs, each including the best medoids found so far, and keeps
+//! the medoids with the lowest total distance over all inputs. Samples are
+//! drawn by a fixed pseudo-random sequence, so results are reproducible.
+//!
+//! PAM swaps a medoid for another input as long as the swap lowers the total
+//! distance by more than a negligible fraction of it, so it runs until no
+//! single swap improves the clustering.
+//!
+//! Inputs infinitely distant from their medoid, such as strings of another
+//! length under Hamming distance, are counted apart from the finite
+//! distances. Medoids are chosen first to leave as few such inputs as
+//! possible, then to minimise the total of the finite distances, and
+//! [`Medoids::cost`] is infinite if any remain.
+
+use crate::clustering::kmedoids::{pam, sample, Cost, Lcg};
+use crate::metric::Metric;
+use crate::threading::assignment::assign_medoids;
+use crate::threading::distances::pairwise_distances;
+
+// Largest input clustered by PAM rather than CLARA
+const PAM_MAX_INPUTS: usize = 1000;
+const CLARA_SAMPLES: usize = 5;
+const CLARA_SEED: u64 = 0x5eed;
+
+/// Medoids of a k-medoids clustering, and the assignment of every input to one
+/// of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Medoids<'a, S: ?Sized = str> {
+    inputs: &'a [&'a S],
+    medoids: Vec<usize>,
+    assignments: Vec<(usize, f64)>,
+}
+
+impl<'a, S: ?Sized> Medoids<'a, S> {
+    /// Medoids, in input order.
+    pub fn medoids(&self) -> Vec<&'a S> {
+        self.medoids.iter().map(|&m| self.inputs[m]).collect()
+    }
+
+    /// Index into [`Medoids::medoids`] of the medoid of each input.
+    pub fn assignments(&self) -> Vec<usize> {
+        self.assignments.iter().map(|&(slot, _)| slot).collect()
+    }
+
+    /// Total distance of every input to its medoid.
+    pub fn cost(&self) -> f64 {
+        self.assignments.iter().map(|&(_, d)| d).sum()
+    }
+
+    /// Members of each medoid's cluster, in input order. Clusters are in the
+    /// order of their medoids, and begin with the medoid if it is the first
+    /// member in input order.
+    pub fn clusters(&self) -> Vec<Vec<&'a S>> {
+        let mut clusters = vec![Vec::new(); self.medoids.len()];
+        for (i, &(slot, _)) in self.assignments.iter().enumerate() {
+            clusters[slot].push(self.inputs[i]);
+        }
+        clusters
+    }
+}
+
+// Inputs must already be validated, with 1 <= k <= inputs.len()
+pub(crate) fn k_medoids<'a, S, M>(
+    inputs: &'a [&'a S],
+    metric: &M,
+    k: usize,
+    n_threads: usize,
+) -> Medoids<'a, S>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let medoids = if inputs.len() <= PAM_MAX_INPUTS {
+        pam(&pairwise_distances(inputs, metric, n_threads), k)
+    } else {
+        clara(inputs, metric, k, n_threads)
+    };
+    let assignments = assign_medoids(inputs, &medoids, metric, n_threads);
+
+    Medoids {
+        inputs,
+        medoids,
+        assignments,
+    }
+}
+
+fn clara<S, M>(inputs: &[&S], metric: &M, k: usize, n_threads: usize) -> Vec<usize>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let mut rng = Lcg::new(CLARA_SEED);
+    let mut best: Option<(Cost, Vec<usize>)> = None;
+
+    for _ in 0..CLARA_SAMPLES {
+        let include = best.as_ref().map_or(&[][..], |(_, medoids)| medoids);
+        let indices = sample(inputs.len(), 40 + 2 * k, &mut rng, include);
+        let sampled: Vec<&S> = indices.iter().map(|&i| inputs[i]).collect();
+
+        let distances = pairwise_distances(&sampled, metric, n_threads.min(sampled.len()));
+        let medoids: Vec<usize> = pam(&distances, k).into_iter().map(|m| indices[m]).collect();
+
+        let cost: Cost = assign_medoids(inputs, &medoids, metric, n_threads)
+            .iter()
+            .map(|&(_, d)| Cost::of(d))
+            .sum();
+        if best.as_ref().is_none_or(|(best, _)| cost < *best) {
+            best = Some((cost, medoids));
+        }
+    }
+    best.expect("at least one sample").1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{k_medoids, PAM_MAX_INPUTS};
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn clusters_grouped_by_medoid() {
+        let inputs = vec!["aaab", "bbbb", "aaaa", "bbcb", "aaba", "cbbb"];
+        let metric = Levenshtein::new(0.0).unwrap();
+        let result = k_medoids(&inputs, &metric, 2, 2);
+        assert_eq!(result.medoids(), vec!["bbbb", "aaaa"]);
+        assert_eq!(result.assignments(), vec![1, 0, 1, 0, 1, 0]);
+        assert_eq!(result.cost(), 4.0);
+        assert_eq!(
+            result.clusters(),
+            vec![vec!["bbbb", "bbcb", "cbbb"], vec!["aaab", "aaaa", "aaba"]]
+        );
+    }
+
+    #[test]
+    fn large_inputs_sampled() {
+        let strings: Vec<String> = (0..PAM_MAX_INPUTS + 200)
+            .map(|i| {
+                let prefix = ["alpha", "kilo", "zulu"][i % 3];
+                format!("{}{:03}", prefix, i % 7)
+            })
+            .collect();
+        let inputs: Vec<&str> = strings.iter().map(|s| s.as_str()).collect();
+        let metric = Levenshtein::new(0.0).unwrap();
+
+        let result = k_medoids(&inputs, &metric, 3, 4);
+        for (cluster, medoid) in result.clusters().iter().zip(result.medoids()) {
+            let prefix = &medoid[..medoid.len() - 3];
+            assert!(cluster.iter().all(|s| s.starts_with(prefix)));
+        }
+        assert_eq!(result, k_medoids(&inputs, &metric, 3, 2));
+    }
+}
@@ -1 +1,160 @@
-pub mod similarity;
+//! String similarity metrics.
+//!
+//! A [`Metric`] decides whether two strings are similar enough to be placed in
+//! the same cluster. [`Levenshtein`] is the default metric used by
+//! [`cluster_strings`](crate::cluster_strings). Custom metrics can be supplied to
+//! [`cluster_strings_with_metric`](crate::cluster_strings_with_metric).
+//!
+//! Edit distance metrics share the `max_edit_frac` threshold of
+//! [`Levenshtein`]:
+//! * [`Levenshtein`]: insertions, deletions and substitutions.
+//! * [`OptimalStringAlignment`]: as above, plus adjacent transpositions.
+//! * [`DamerauLevenshtein`]: as above, with repeated edits of a substring.
+//! * [`WeightedLevenshtein`]: insertions, deletions and substitutions with custom
+//!   costs, e.g. cheaper substitutions of adjacent keys or OCR confusions.
+//! * [`Hamming`]: substitutions only, for fixed-length codes.
+//!
+//! By default the maximum edit distance is `max_edit_frac` of the shorter
+//! string's length. Other policies can be selected with [`EditThreshold`], and
+//! the resulting maximum can be clamped to a range of edit counts.
+//!
+//! Edit distance metrics count lengths and edits in Unicode scalar values by
+//! default, or in grapheme clusters if configured with [`Unit::Grapheme`].
+//! They compare strings case-sensitively and with significant whitespace by
+//! default. [`Case`] and [`Whitespace`] options fold case and collapse or
+//! ignore whitespace symbol by symbol as strings are compared, into buffers
+//! reused by each thread, and clusters contain the original strings.
+//!
+//! Similarity metrics are thresholded on a minimum similarity in \[0,1\]:
+//! * [`JaroWinkler`]: Jaro similarity boosted by the length of the common prefix.
+//! * [`Jaccard`]: shared character q-grams over all distinct q-grams.
+//! * [`SorensenDice`]: twice the shared character q-grams over the q-grams of each string.
+//! * [`TokenSortRatio`]: character similarity of the sorted tokens.
+//! * [`TokenSetRatio`]: character similarity of the shared and distinct token sets.
+//! * [`LcsRatio`]: twice the longest common subsequence over the combined length.
+//! * [`LongestCommonSubstring`]: twice the longest common substring over the combined length.
+//! * [`TfIdfCosine`]: cosine similarity of token or q-gram vectors weighted by
+//!   their rarity among the inputs.
+//! * [`NeedlemanWunsch`]: global alignment score with affine gap penalties.
+//! * [`SmithWaterman`]: local alignment score with affine gap penalties.
+//!
+//! [`Phonetic`] compares names by pronunciation, either requiring equal
+//! Soundex, Double Metaphone or NYSIIS codes or applying the Levenshtein rule
+//! to the codes.
+//!
+//! [`Fields`] compares delimited records such as "first | last | city" field by
+//! field, with a separate metric for each field.
+//!
+//! Besides deciding similarity, every metric reports a [`Metric::distance`]:
+//! the number of edits for edit distance metrics and one minus the similarity
+//! for similarity metrics.
+//!
+//! Any metric can be wrapped in [`Normalized`] to compare strings after case
+//! folding, Unicode normalization, diacritic stripping, whitespace collapsing,
+//! punctuation removal or custom replacements configured on a [`Normalizer`].
+//!
+//! # Examples
+//! Custom metric:
+//! ```
+//! # fn main() -> Result<(), clustr::ValueError> {
+//! use clustr::metric::Metric;
+//!
+//! struct SameFirstChar;
+//!
+//! impl Metric for SameFirstChar {
+//!     fn is_similar(&self, a: &str, b: &str) -> bool {
+//!         a.chars().next() == b.chars().next()
+//!     }
+//! }
+//!
+//! let inputs = vec!["apple", "avocado", "banana"];
+//! let expected = vec![vec!["apple", "avocado"], vec!["banana"]];
+//!
+//! let clusters = clustr::cluster_strings_with_metric(&inputs, SameFirstChar, 1)?;
+//!
+//! assert_eq!(clusters, expected);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+
+mod alignment;
+mod config;
+mod damerau;
+mod fields;
+mod fold;
+mod hamming;
+mod jaro;
+mod kernel;
+mod lcs;
+mod levenshtein;
+mod normalize;
+mod phonetic;
+mod qgram;
+#[cfg(test)]
+mod testing;
+mod tfidf;
+mod threshold;
+mod token;
+mod unit;
+mod weighted;
+
+use crate::ValueError;
+
+pub use alignment::{NeedlemanWunsch, SmithWaterman, SubstitutionMatrix};
+pub use damerau::{DamerauLevenshtein, OptimalStringAlignment};
+pub use fields::{Combination, Fields};
+pub use fold::{Case, Whitespace};
+pub use hamming::{Hamming, LengthPolicy};
+pub use jaro::JaroWinkler;
+pub use lcs::{LcsRatio, LongestCommonSubstring};
+pub use levenshtein::{Levenshtein, LevenshteinKernel};
+pub use normalize::{Normalized, Normalizer};
+pub use phonetic::{Phonetic, PhoneticEncoder};
+pub use qgram::{Jaccard, SorensenDice};
+pub use tfidf::TfIdfCosine;
+pub use threshold::EditThreshold;
+pub use token::{TokenSetRatio, TokenSortRatio, Tokenizer};
+pub use unit::Unit;
+pub use weighted::{SubstitutionTable, WeightedLevenshtein};
+
+/// Pairwise similarity used to form and merge clusters.
+///
+/// Metrics compare strings by default. Metrics over other sequences, such as
+/// bytes or token ids, implement `Metric<[T]>` and are used with
+/// [`cluster_sequences_with_metric`](crate::cluster_sequences_with_metric).
+///
+/// Metrics are shared between the clustering threads and must therefore be `Sync`.
+pub trait Metric<S: ?Sized = str>: Sync {
+    /// Returns true if `a` and `b` belong in the same cluster.
+    fn is_similar(&self, a: &S, b: &S) -> bool;
+
+    /// Distance between `a` and `b`, where smaller values are more similar.
+    ///
+    /// Edit distance metrics return the number of edits and similarity metrics
+    /// return one minus the similarity. Returns 0 if `a` and `b` are similar
+    /// and 1 otherwise by default.
+    fn distance(&self, a: &S, b: &S) -> f64 {
+        if self.is_similar(a, b) {
+            0.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Check that the metric can compare `inputs` before clustering starts.
+    ///
+    /// Errors cannot be propagated out of the clustering threads, so metrics
+    /// with requirements on their inputs should reject them here. Accepts all
+    /// inputs by default.
+    fn validate(&self, inputs: &[&S]) -> Result<(), ValueError> {
+        let _ = inputs;
+        Ok(())
+    }
+
+    /// Prepare the metric to compare `inputs`, e.g. by computing corpus
+    /// statistics, before clustering starts. Does nothing by default.
+    fn fit(&mut self, inputs: &[&S]) {
+        let _ = inputs;
+    }
+}
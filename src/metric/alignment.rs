@@ -0,0 +1,514 @@
+use super::kernel::{global_alignment_score, local_alignment_score};
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+use std::collections::HashMap;
+
+/// Scores of aligning pairs of characters with one another.
+///
+/// Scores are symmetric. Pairs that are not in the matrix score the default
+/// match score if the characters are equal and the default mismatch score
+/// otherwise.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubstitutionMatrix {
+    match_score: f32,
+    mismatch_score: f32,
+    scores: HashMap<(char, char), f32>,
+}
+
+impl SubstitutionMatrix {
+    /// Create a matrix scoring equal characters `match_score` and different
+    /// characters `mismatch_score`.
+    ///
+    /// Returns [`ValueError::InvalidScore`] if either score is not finite or
+    /// `match_score` is not positive.
+    pub fn new(match_score: f32, mismatch_score: f32) -> Result<SubstitutionMatrix, ValueError> {
+        check_score(match_score, true)?;
+        check_score(mismatch_score, false)?;
+        Ok(SubstitutionMatrix {
+            match_score,
+            mismatch_score,
+            scores: HashMap::new(),
+        })
+    }
+
+    /// Matrix for nucleotides in which transitions (A↔G, C↔T) score
+    /// `transition_score`, so that they are penalised less than transversions.
+    /// Nucleotides are matched regardless of case.
+    ///
+    /// Returns [`ValueError::InvalidScore`] if a score is not finite or
+    /// `match_score` is not positive.
+    pub fn nucleotide(
+        match_score: f32,
+        transition_score: f32,
+        transversion_score: f32,
+    ) -> Result<SubstitutionMatrix, ValueError> {
+        let mut matrix = SubstitutionMatrix::new(match_score, transversion_score)?;
+        for (a, b) in [('A', 'G'), ('C', 'T')] {
+            for a in [a, a.to_ascii_lowercase()] {
+                for b in [b, b.to_ascii_lowercase()] {
+                    matrix = matrix.with_score(a, b, transition_score)?;
+                }
+            }
+        }
+        for c in ['A', 'C', 'G', 'T'] {
+            matrix = matrix.with_score(c, c.to_ascii_lowercase(), match_score)?;
+        }
+        Ok(matrix)
+    }
+
+    /// Set the score of aligning `a` with `b`, and `b` with `a`.
+    ///
+    /// Returns [`ValueError::InvalidScore`] if `score` is not finite, or is
+    /// not positive for a character aligned with itself.
+    pub fn with_score(
+        mut self,
+        a: char,
+        b: char,
+        score: f32,
+    ) -> Result<SubstitutionMatrix, ValueError> {
+        check_score(score, a == b)?;
+        self.scores.insert((a, b), score);
+        self.scores.insert((b, a), score);
+        Ok(self)
+    }
+
+    /// Returns the score of aligning `a` with `b`.
+    pub fn get(&self, a: char, b: char) -> f32 {
+        match self.scores.get(&(a, b)) {
+            Some(&score) => score,
+            None if a == b => self.match_score,
+            None => self.mismatch_score,
+        }
+    }
+
+    fn get_symbols<T: Eq>(&self, a: &T, b: &T) -> f64 {
+        if a == b {
+            self.match_score as f64
+        } else {
+            self.mismatch_score as f64
+        }
+    }
+}
+
+impl Default for SubstitutionMatrix {
+    /// Matrix scoring matches 1 and mismatches -1.
+    fn default() -> SubstitutionMatrix {
+        SubstitutionMatrix::new(1.0, -1.0).expect("default scores are valid")
+    }
+}
+
+fn check_score(score: f32, is_match: bool) -> Result<(), ValueError> {
+    if !score.is_finite() || (is_match && score <= 0.0) {
+        return Err(ValueError::InvalidScore);
+    }
+    Ok(())
+}
+
+fn check_penalty(penalty: f32) -> Result<(), ValueError> {
+    if !penalty.is_finite() || penalty < 0.0 {
+        return Err(ValueError::InvalidCost);
+    }
+    Ok(())
+}
+
+// Scoring shared by the global and local alignment metrics
+#[derive(Clone, Debug, PartialEq)]
+struct Scoring {
+    matrix: SubstitutionMatrix,
+    gap_open: f32,
+    gap_extend: f32,
+}
+
+impl Default for Scoring {
+    fn default() -> Scoring {
+        Scoring {
+            matrix: SubstitutionMatrix::default(),
+            gap_open: 2.0,
+            gap_extend: 0.5,
+        }
+    }
+}
+
+impl Scoring {
+    fn with_gap_penalties(self, open: f32, extend: f32) -> Result<Scoring, ValueError> {
+        check_penalty(open)?;
+        check_penalty(extend)?;
+        Ok(Scoring {
+            gap_open: open,
+            gap_extend: extend,
+            ..self
+        })
+    }
+
+    // Score of aligning a string with itself
+    fn self_score(&self, s: &[char]) -> f64 {
+        s.iter().map(|&c| self.matrix.get(c, c) as f64).sum()
+    }
+
+    fn global(&self, a: &[char], b: &[char]) -> f64 {
+        let score = global_alignment_score(a, b, self.open(), self.extend(), |&x, &y| {
+            self.matrix.get(x, y) as f64
+        });
+        let best = self.self_score(a).max(self.self_score(b));
+        normalize(score, best, a.is_empty() && b.is_empty())
+    }
+
+    fn local(&self, a: &[char], b: &[char]) -> f64 {
+        let score = local_alignment_score(a, b, self.open(), self.extend(), |&x, &y| {
+            self.matrix.get(x, y) as f64
+        });
+        let best = self.self_score(a).min(self.self_score(b));
+        normalize(score, best, a.is_empty() && b.is_empty())
+    }
+
+    // Sequences other than strings are scored with the default scores
+    fn global_symbols<T: Eq>(&self, a: &[T], b: &[T]) -> f64 {
+        let score = global_alignment_score(a, b, self.open(), self.extend(), |x, y| {
+            self.matrix.get_symbols(x, y)
+        });
+        let best = a.len().max(b.len()) as f64 * self.matrix.match_score as f64;
+        normalize(score, best, a.is_empty() && b.is_empty())
+    }
+
+    fn local_symbols<T: Eq>(&self, a: &[T], b: &[T]) -> f64 {
+        let score = local_alignment_score(a, b, self.open(), self.extend(), |x, y| {
+            self.matrix.get_symbols(x, y)
+        });
+        let best = a.len().min(b.len()) as f64 * self.matrix.match_score as f64;
+        normalize(score, best, a.is_empty() && b.is_empty())
+    }
+
+    fn open(&self) -> f64 {
+        self.gap_open as f64
+    }
+
+    fn extend(&self) -> f64 {
+        self.gap_extend as f64
+    }
+}
+
+// Scale an alignment score by the best achievable score, clamping to [0,1]
+fn normalize(score: f64, best: f64, both_empty: bool) -> f64 {
+    if both_empty {
+        return 1.0;
+    }
+    if best <= 0.0 {
+        return 0.0;
+    }
+    (score / best).clamp(0.0, 1.0)
+}
+
+fn chars(a: &str, b: &str) -> (Vec<char>, Vec<char>) {
+    (a.chars().collect(), b.chars().collect())
+}
+
+/// Global alignment (Needleman-Wunsch) score with affine gap penalties,
+/// thresholded on a minimum similarity.
+///
+/// Both strings are aligned end to end. Aligned characters are scored with a
+/// [`SubstitutionMatrix`], and a gap of `k` characters is penalised
+/// `gap_open + (k - 1) * gap_extend`, so one long insertion costs much less
+/// than as many scattered edits. The similarity is the alignment score over
+/// the score of aligning the longer string with itself, clamped to \[0,1\].
+///
+/// Matches score 1, mismatches -1, and gaps cost 2 to open and 0.5 to extend by
+/// default. Sequences other than strings are scored with the matrix's default
+/// match and mismatch scores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NeedlemanWunsch {
+    threshold: f32,
+    scoring: Scoring,
+}
+
+impl NeedlemanWunsch {
+    /// Create a new metric with the default scores.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<NeedlemanWunsch, ValueError> {
+        check_fraction(threshold)?;
+        Ok(NeedlemanWunsch {
+            threshold,
+            scoring: Scoring::default(),
+        })
+    }
+
+    /// Set the penalties of opening a gap and of extending it by each further
+    /// character.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if a penalty is negative or not finite.
+    pub fn with_gap_penalties(
+        mut self,
+        open: f32,
+        extend: f32,
+    ) -> Result<NeedlemanWunsch, ValueError> {
+        self.scoring = self.scoring.with_gap_penalties(open, extend)?;
+        Ok(self)
+    }
+
+    /// Set the scores of aligned characters.
+    pub fn with_substitution_matrix(mut self, matrix: SubstitutionMatrix) -> NeedlemanWunsch {
+        self.scoring.matrix = matrix;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = chars(a, b);
+        self.scoring.global(&a, &b)
+    }
+}
+
+impl Metric for NeedlemanWunsch {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for NeedlemanWunsch {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        self.scoring.global_symbols(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        1.0 - self.scoring.global_symbols(a, b)
+    }
+}
+
+/// Local alignment (Smith-Waterman) score with affine gap penalties,
+/// thresholded on a minimum similarity.
+///
+/// Scored like [`NeedlemanWunsch`], but only the best aligned pair of
+/// substrings counts, so unrelated prefixes and suffixes are ignored. The
+/// similarity is the local alignment score over the score of aligning the
+/// shorter string with itself, clamped to \[0,1\], which suits strings embedded
+/// in longer ones such as motifs in reads or messages in log lines.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmithWaterman {
+    threshold: f32,
+    scoring: Scoring,
+}
+
+impl SmithWaterman {
+    /// Create a new metric with the default scores.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<SmithWaterman, ValueError> {
+        check_fraction(threshold)?;
+        Ok(SmithWaterman {
+            threshold,
+            scoring: Scoring::default(),
+        })
+    }
+
+    /// Set the penalties of opening a gap and of extending it by each further
+    /// character.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if a penalty is negative or not finite.
+    pub fn with_gap_penalties(
+        mut self,
+        open: f32,
+        extend: f32,
+    ) -> Result<SmithWaterman, ValueError> {
+        self.scoring = self.scoring.with_gap_penalties(open, extend)?;
+        Ok(self)
+    }
+
+    /// Set the scores of aligned characters.
+    pub fn with_substitution_matrix(mut self, matrix: SubstitutionMatrix) -> SmithWaterman {
+        self.scoring.matrix = matrix;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = chars(a, b);
+        self.scoring.local(&a, &b)
+    }
+}
+
+impl Metric for SmithWaterman {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for SmithWaterman {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        self.scoring.local_symbols(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        1.0 - self.scoring.local_symbols(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NeedlemanWunsch, SmithWaterman, SubstitutionMatrix};
+    use crate::metric::testing::assert_close;
+    use crate::metric::{Levenshtein, Metric};
+    use crate::ValueError;
+
+    mod substitution_matrix {
+        use super::{SubstitutionMatrix, ValueError};
+
+        #[test]
+        fn invalid_scores_rejected() {
+            assert_eq!(
+                SubstitutionMatrix::new(0.0, -1.0),
+                Err(ValueError::InvalidScore)
+            );
+            assert_eq!(
+                SubstitutionMatrix::new(1.0, f32::NAN),
+                Err(ValueError::InvalidScore)
+            );
+            let result = SubstitutionMatrix::default().with_score('a', 'a', -1.0);
+            assert_eq!(result, Err(ValueError::InvalidScore));
+        }
+
+        #[test]
+        fn scores_symmetric() {
+            let matrix = SubstitutionMatrix::default()
+                .with_score('a', 'b', 0.5)
+                .unwrap();
+            assert_eq!(matrix.get('a', 'b'), 0.5);
+            assert_eq!(matrix.get('b', 'a'), 0.5);
+        }
+
+        #[test]
+        fn default_scores_applied() {
+            let matrix = SubstitutionMatrix::new(2.0, -3.0).unwrap();
+            assert_eq!(matrix.get('a', 'a'), 2.0);
+            assert_eq!(matrix.get('a', 'b'), -3.0);
+        }
+
+        #[test]
+        fn nucleotide_transitions_scored() {
+            let matrix = SubstitutionMatrix::nucleotide(2.0, -1.0, -2.0).unwrap();
+            assert_eq!(matrix.get('A', 'G'), -1.0);
+            assert_eq!(matrix.get('t', 'C'), -1.0);
+            assert_eq!(matrix.get('A', 'T'), -2.0);
+            assert_eq!(matrix.get('a', 'A'), 2.0);
+        }
+    }
+
+    mod needleman_wunsch {
+        use super::{assert_close, Levenshtein, Metric, NeedlemanWunsch, SubstitutionMatrix};
+        use crate::ValueError;
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(NeedlemanWunsch::new(1.5), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn gap_penalties_rejected_if_negative() {
+            let result = NeedlemanWunsch::new(0.5)
+                .unwrap()
+                .with_gap_penalties(-1.0, 0.5);
+            assert_eq!(result, Err(ValueError::InvalidCost));
+        }
+
+        #[test]
+        fn similarity_normalized_by_longer_string() {
+            let metric = NeedlemanWunsch::new(0.0).unwrap();
+            assert_close(metric.similarity("GATTACA", "GATTACA"), 1.0);
+            // Seven matches and a gap of three characters
+            assert_close(
+                metric.similarity("GATTACA", "GATTXXXACA"),
+                (7.0 - 3.0) / 10.0,
+            );
+            assert_close(metric.similarity("abc", "xyz"), 0.0);
+        }
+
+        #[test]
+        fn long_insertion_tolerated() {
+            let metric = NeedlemanWunsch::new(0.5)
+                .unwrap()
+                .with_gap_penalties(1.0, 0.1)
+                .unwrap();
+            let a = "connection reset by peer";
+            let b = "connection 10.0.0.1:443 reset by peer";
+            assert!(metric.is_similar(a, b));
+            assert!(!Levenshtein::new(0.5).unwrap().is_similar(a, b));
+        }
+
+        #[test]
+        fn substitution_matrix_applied() {
+            let matrix = SubstitutionMatrix::nucleotide(1.0, 0.5, -1.0).unwrap();
+            let metric = NeedlemanWunsch::new(0.0)
+                .unwrap()
+                .with_substitution_matrix(matrix);
+            assert_close(metric.similarity("ACGT", "GCGT"), 3.5 / 4.0);
+            assert_close(metric.similarity("ACGT", "TCGT"), 2.0 / 4.0);
+        }
+
+        #[test]
+        fn handles_empty() {
+            let metric = NeedlemanWunsch::new(1.0).unwrap();
+            assert!(metric.is_similar("", ""));
+            assert_close(metric.similarity("", "abc"), 0.0);
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = NeedlemanWunsch::new(0.0).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[1, 2, 3, 4]);
+            assert_close(metric.distance(a, b), 0.0);
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[1, 2, 9, 4]);
+            assert_close(metric.distance(a, b), 0.5);
+        }
+    }
+
+    mod smith_waterman {
+        use super::{assert_close, Metric, NeedlemanWunsch, SmithWaterman};
+        use crate::ValueError;
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(SmithWaterman::new(-0.5), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn similarity_normalized_by_shorter_string() {
+            let metric = SmithWaterman::new(0.0).unwrap();
+            assert_close(metric.similarity("GATTACA", "xxxGATTACAyyy"), 1.0);
+            assert_close(
+                metric.similarity("GATTACA", "xxxGATACAyyy"),
+                (6.0 - 2.0) / 7.0,
+            );
+        }
+
+        #[test]
+        fn unrelated_flanks_ignored() {
+            let a = "ERROR disk /dev/sda1 full";
+            let b = "2024-01-01T00:00:00Z host-7 ERROR disk /dev/sda1 full (code 28)";
+            assert!(SmithWaterman::new(0.9).unwrap().is_similar(a, b));
+            assert!(!NeedlemanWunsch::new(0.9).unwrap().is_similar(a, b));
+        }
+
+        #[test]
+        fn handles_empty() {
+            let metric = SmithWaterman::new(0.0).unwrap();
+            assert_close(metric.similarity("", ""), 1.0);
+            assert_close(metric.similarity("", "abc"), 0.0);
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = SmithWaterman::new(1.0).unwrap();
+            let (a, b): (&[u8], &[u8]) = (b"ACGT", b"TTACGTTT");
+            assert!(metric.is_similar(a, b));
+        }
+    }
+}
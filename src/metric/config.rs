@@ -0,0 +1,83 @@
+use super::fold::Folding;
+use super::threshold::EditLimit;
+use super::{EditThreshold, Unit};
+use crate::ValueError;
+
+// Threshold, unit and case and whitespace handling shared by the edit distance
+// metrics, configured through the builder methods of `edit_config_builders`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EditConfig {
+    pub limit: EditLimit,
+    pub unit: Unit,
+    pub folding: Folding,
+}
+
+impl EditConfig {
+    pub fn new(max_edit_frac: f32) -> Result<EditConfig, ValueError> {
+        Ok(EditConfig {
+            limit: EditLimit::new(EditThreshold::FractionOfShorter(max_edit_frac))?,
+            unit: Unit::Char,
+            folding: Folding::default(),
+        })
+    }
+}
+
+// Builder methods setting the `config: EditConfig` field of an edit distance
+// metric. `edits` names what the threshold bounds, e.g. "edits". The unit is
+// only configurable on metrics listing `unit`.
+macro_rules! edit_config_builders {
+    ($metric:ident, $edits:literal, unit) => {
+        edit_config_builders!($metric, $edits);
+
+        impl $metric {
+            /// Set the unit in which lengths and edits are measured.
+            pub fn with_unit(mut self, unit: $crate::metric::Unit) -> $metric {
+                self.config.unit = unit;
+                self
+            }
+        }
+    };
+    ($metric:ident, $edits:literal) => {
+        impl $metric {
+            #[doc = concat!("Set the maximum ", $edits, " between similar strings, replacing")]
+            /// `max_edit_frac`.
+            ///
+            /// Returns [`ValueError::InvalidFraction`] if a fractional threshold is
+            /// outside of the closed interval \[0,1\].
+            pub fn with_threshold(
+                mut self,
+                threshold: $crate::metric::EditThreshold,
+            ) -> Result<$metric, $crate::ValueError> {
+                self.config.limit = self.config.limit.with_threshold(threshold)?;
+                Ok(self)
+            }
+
+            #[doc = concat!("Clamp the maximum ", $edits, " derived from the threshold to the")]
+            /// closed interval \[min_edits,max_edits\].
+            ///
+            /// Returns [`ValueError::InvalidEditBounds`] if `min_edits` exceeds `max_edits`.
+            pub fn with_edit_bounds(
+                mut self,
+                min_edits: usize,
+                max_edits: usize,
+            ) -> Result<$metric, $crate::ValueError> {
+                self.config.limit = self.config.limit.with_bounds(min_edits, max_edits)?;
+                Ok(self)
+            }
+
+            /// Set the case sensitivity of comparisons.
+            pub fn with_case(mut self, case: $crate::metric::Case) -> $metric {
+                self.config.folding.case = case;
+                self
+            }
+
+            /// Set the handling of whitespace in comparisons.
+            pub fn with_whitespace(mut self, whitespace: $crate::metric::Whitespace) -> $metric {
+                self.config.folding.whitespace = whitespace;
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use edit_config_builders;
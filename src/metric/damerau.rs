@@ -0,0 +1,254 @@
+use super::config::{edit_config_builders, EditConfig};
+use super::kernel::{damerau_levenshtein_distance, osa_distance};
+use super::threshold::within_max_edit;
+use super::Metric;
+use crate::ValueError;
+
+use std::hash::Hash;
+
+/// Optimal string alignment distance thresholded on a fraction of the shorter
+/// string's length.
+///
+/// Like [`Levenshtein`](super::Levenshtein), but an adjacent transposition
+/// ("teh" and "the") counts as a single edit. No substring may be edited more
+/// than once, so "ca" and "abc" are three edits apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimalStringAlignment {
+    config: EditConfig,
+}
+
+impl OptimalStringAlignment {
+    /// Create a new metric.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(max_edit_frac: f32) -> Result<OptimalStringAlignment, ValueError> {
+        Ok(OptimalStringAlignment {
+            config: EditConfig::new(max_edit_frac)?,
+        })
+    }
+}
+
+edit_config_builders!(OptimalStringAlignment, "number of edits", unit);
+
+impl Metric for OptimalStringAlignment {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| self.is_similar(a, b))
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| self.distance(a, b))
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for OptimalStringAlignment {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        within_max_edit(a.len(), b.len(), &self.config.limit, |_| osa_distance(a, b))
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        osa_distance(a, b) as f64
+    }
+}
+
+/// Damerau-Levenshtein distance thresholded on a fraction of the shorter
+/// string's length.
+///
+/// Like [`OptimalStringAlignment`], but substrings may be edited more than
+/// once, so "ca" and "abc" are two edits apart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DamerauLevenshtein {
+    config: EditConfig,
+}
+
+impl DamerauLevenshtein {
+    /// Create a new metric.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(max_edit_frac: f32) -> Result<DamerauLevenshtein, ValueError> {
+        Ok(DamerauLevenshtein {
+            config: EditConfig::new(max_edit_frac)?,
+        })
+    }
+}
+
+edit_config_builders!(DamerauLevenshtein, "number of edits", unit);
+
+impl Metric for DamerauLevenshtein {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| self.is_similar(a, b))
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| self.distance(a, b))
+    }
+}
+
+impl<T: Eq + Hash + Sync> Metric<[T]> for DamerauLevenshtein {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        within_max_edit(a.len(), b.len(), &self.config.limit, |_| {
+            damerau_levenshtein_distance(a, b)
+        })
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        damerau_levenshtein_distance(a, b) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DamerauLevenshtein, OptimalStringAlignment};
+    use crate::metric::{EditThreshold, Metric, Unit};
+    use crate::ValueError;
+
+    mod optimal_string_alignment {
+        use super::{Metric, OptimalStringAlignment, Unit, ValueError};
+        use crate::metric::{Case, Whitespace};
+
+        #[test]
+        fn new_rejects_invalid_fraction() {
+            let result = OptimalStringAlignment::new(1.1);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn accepts_transposition_as_one_edit() {
+            let metric = OptimalStringAlignment::new(0.25).unwrap();
+            assert!(metric.is_similar("abcd", "abdc"));
+        }
+
+        #[test]
+        fn rejects_above_max_edit() {
+            let metric = OptimalStringAlignment::new(0.34).unwrap();
+            assert!(!metric.is_similar("cab", "abc"));
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = OptimalStringAlignment::new(0.25).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[1, 2, 4, 3]);
+            assert!(metric.is_similar(a, b));
+        }
+
+        #[test]
+        fn edit_bounds_applied() {
+            let metric = OptimalStringAlignment::new(0.0)
+                .unwrap()
+                .with_edit_bounds(1, 1)
+                .unwrap();
+            assert!(metric.is_similar("teh", "the"));
+        }
+
+        #[test]
+        fn measures_multi_byte_strings_in_chars() {
+            let metric = OptimalStringAlignment::new(0.5).unwrap();
+            assert!(!metric.is_similar("日本", "中国"));
+            assert!(metric.is_similar("日本", "本日"));
+        }
+
+        #[test]
+        fn grapheme_unit_applied() {
+            let metric = OptimalStringAlignment::new(0.5).unwrap();
+            assert!(!metric.is_similar("🇿🇦🇺🇸", "🇺🇸🇿🇦"));
+            let metric = metric.with_unit(Unit::Grapheme);
+            assert!(metric.is_similar("🇿🇦🇺🇸", "🇺🇸🇿🇦"));
+        }
+
+        #[test]
+        fn case_and_whitespace_handling_applied() {
+            let metric = OptimalStringAlignment::new(0.0)
+                .unwrap()
+                .with_case(Case::InsensitiveAscii)
+                .with_whitespace(Whitespace::Collapsed);
+            assert!(metric.is_similar("The  Cat", "the cat"));
+            assert_eq!(metric.distance("TEH cat ", "the cat"), 1.0);
+        }
+
+        #[test]
+        fn distance_counts_edits() {
+            let metric = OptimalStringAlignment::new(0.0).unwrap();
+            assert_eq!(metric.distance("ca", "ac"), 1.0);
+            assert_eq!(metric.distance("ca", "abc"), 3.0);
+        }
+    }
+
+    mod damerau_levenshtein {
+        use super::{DamerauLevenshtein, EditThreshold, Metric, Unit, ValueError};
+        use crate::metric::{Case, Whitespace};
+
+        #[test]
+        fn new_rejects_invalid_fraction() {
+            let result = DamerauLevenshtein::new(-0.1);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn accepts_transposition_as_one_edit() {
+            let metric = DamerauLevenshtein::new(0.25).unwrap();
+            assert!(metric.is_similar("abcd", "abdc"));
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = DamerauLevenshtein::new(0.25).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[1, 2, 4, 3]);
+            assert!(metric.is_similar(a, b));
+        }
+
+        #[test]
+        fn accepts_repeated_substring_edits() {
+            let metric = DamerauLevenshtein::new(1.0).unwrap();
+            assert!(metric.is_similar("ca", "abc"));
+        }
+
+        #[test]
+        fn threshold_policy_applied() {
+            let metric = DamerauLevenshtein::new(0.0)
+                .unwrap()
+                .with_threshold(EditThreshold::Absolute(1))
+                .unwrap();
+            assert!(metric.is_similar("teh", "the"));
+        }
+
+        #[test]
+        fn measures_multi_byte_strings_in_chars() {
+            let metric = DamerauLevenshtein::new(0.5).unwrap();
+            assert!(!metric.is_similar("日本", "中国"));
+        }
+
+        #[test]
+        fn grapheme_unit_applied() {
+            let metric = DamerauLevenshtein::new(0.5).unwrap();
+            assert!(!metric.is_similar("🇿🇦🇺🇸", "🇺🇸🇿🇦"));
+            let metric = metric.with_unit(Unit::Grapheme);
+            assert!(metric.is_similar("🇿🇦🇺🇸", "🇺🇸🇿🇦"));
+        }
+
+        #[test]
+        fn case_and_whitespace_handling_applied() {
+            let metric = DamerauLevenshtein::new(0.0)
+                .unwrap()
+                .with_case(Case::Insensitive)
+                .with_whitespace(Whitespace::Ignored);
+            assert!(metric.is_similar("Grüße aus", "GRÜSSEAUS"));
+        }
+
+        #[test]
+        fn distance_counts_edits() {
+            let metric = DamerauLevenshtein::new(0.0).unwrap();
+            assert_eq!(metric.distance("ca", "abc"), 2.0);
+            let (a, b): (&[u8], &[u8]) = (b"abcd", b"badc");
+            assert_eq!(metric.distance(a, b), 2.0);
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+/// Rule combining the per-field decisions of [`Fields`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Combination {
+    /// Records are similar if every field is similar.
+    #[default]
+    All,
+    /// Records are similar if any field is similar.
+    Any,
+    /// Records are similar if the weights of the similar fields make up at
+    /// least the given fraction of the total weight. Each field votes with its
+    /// weight on its own similar or dissimilar decision, so a field that is
+    /// barely dissimilar counts as much as one that is entirely different.
+    WeightedVote(f32),
+}
+
+struct Field {
+    metric: Box<dyn Metric>,
+    weight: f32,
+}
+
+/// Composite metric comparing structured records field by field.
+///
+/// Records are split on a delimiter and surrounding whitespace is trimmed
+/// from each field, so "Jane | Smith | Leeds" has the fields "Jane", "Smith"
+/// and "Leeds". Each field is compared with its own metric, and the per-field
+/// decisions are combined according to a [`Combination`]. The distance
+/// between records is the fraction of the total weight carried by fields that
+/// are not similar.
+///
+/// # Examples
+/// ```
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::{Fields, JaroWinkler, Levenshtein, Metric};
+///
+/// let metric = Fields::new("|")?
+///     .with_field(JaroWinkler::new(0.85)?)
+///     .with_field(Levenshtein::new(0.2)?)
+///     .with_field(Levenshtein::new(0.0)?);
+///
+/// assert!(metric.is_similar("Jon | Smith | Leeds", "John | Smyth | Leeds"));
+/// assert!(!metric.is_similar("Jon | Smith | Leeds", "John | Smyth | York"));
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct Fields {
+    delimiter: String,
+    fields: Vec<Field>,
+    combination: Combination,
+}
+
+impl Fields {
+    /// Create a new metric splitting records on `delimiter`, with no fields
+    /// and requiring [`Combination::All`] fields to be similar.
+    ///
+    /// Returns [`ValueError::InvalidDelimiter`] if `delimiter` is empty.
+    pub fn new(delimiter: &str) -> Result<Fields, ValueError> {
+        if delimiter.is_empty() {
+            return Err(ValueError::InvalidDelimiter);
+        }
+        Ok(Fields {
+            delimiter: delimiter.to_string(),
+            fields: Vec::new(),
+            combination: Combination::All,
+        })
+    }
+
+    /// Append a field compared with `metric`, with a weight of one.
+    pub fn with_field<M: Metric + 'static>(mut self, metric: M) -> Fields {
+        self.fields.push(Field {
+            metric: Box::new(metric),
+            weight: 1.0,
+        });
+        self
+    }
+
+    /// Append a field compared with `metric`, contributing `weight` to
+    /// [`Combination::WeightedVote`].
+    ///
+    /// Returns [`ValueError::InvalidWeight`] if `weight` is not positive and finite.
+    pub fn with_weighted_field<M: Metric + 'static>(
+        mut self,
+        metric: M,
+        weight: f32,
+    ) -> Result<Fields, ValueError> {
+        if !(weight.is_finite() && weight > 0.0) {
+            return Err(ValueError::InvalidWeight);
+        }
+        self.fields.push(Field {
+            metric: Box::new(metric),
+            weight,
+        });
+        Ok(self)
+    }
+
+    /// Set the rule combining the per-field decisions.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if a weighted threshold is
+    /// outside of the closed interval \[0,1\].
+    pub fn with_combination(mut self, combination: Combination) -> Result<Fields, ValueError> {
+        if let Combination::WeightedVote(threshold) = combination {
+            check_fraction(threshold)?;
+        }
+        self.combination = combination;
+        Ok(self)
+    }
+
+    /// Returns true if the pre-split records `a` and `b` are similar. Missing
+    /// fields are compared as empty strings.
+    pub fn is_similar_fields(&self, a: &[&str], b: &[&str]) -> bool {
+        let mut decisions = self.fields.iter().enumerate().map(|(i, field)| {
+            let x = a.get(i).copied().unwrap_or_default();
+            let y = b.get(i).copied().unwrap_or_default();
+            (field, field.metric.is_similar(x, y))
+        });
+
+        match self.combination {
+            Combination::All => decisions.all(|(_, similar)| similar),
+            Combination::Any => decisions.any(|(_, similar)| similar),
+            Combination::WeightedVote(threshold) => {
+                let total: f32 = self.fields.iter().map(|f| f.weight).sum();
+                let similar: f32 = decisions
+                    .filter(|(_, similar)| *similar)
+                    .map(|(field, _)| field.weight)
+                    .sum();
+                similar >= threshold * total
+            }
+        }
+    }
+
+    /// Returns the fraction of the total weight carried by fields of the
+    /// pre-split records `a` and `b` that are not similar.
+    pub fn distance_fields(&self, a: &[&str], b: &[&str]) -> f64 {
+        let total: f32 = self.fields.iter().map(|f| f.weight).sum();
+        if total == 0.0 {
+            return 0.0;
+        }
+        let dissimilar: f32 = self
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(i, field)| {
+                let x = a.get(*i).copied().unwrap_or_default();
+                let y = b.get(*i).copied().unwrap_or_default();
+                !field.metric.is_similar(x, y)
+            })
+            .map(|(_, field)| field.weight)
+            .sum();
+        (dissimilar / total) as f64
+    }
+
+    fn split<'a>(&self, record: &'a str) -> Vec<&'a str> {
+        record
+            .split(self.delimiter.as_str())
+            .map(str::trim)
+            .collect()
+    }
+
+    fn validate_records(&self, records: &[&[&str]]) -> Result<(), ValueError> {
+        if records.iter().any(|r| r.len() != self.fields.len()) {
+            return Err(ValueError::FieldCountMismatch);
+        }
+        for (i, field) in self.fields.iter().enumerate() {
+            field.metric.validate(&Fields::column(records, i))?;
+        }
+        Ok(())
+    }
+
+    fn fit_records(&mut self, records: &[&[&str]]) {
+        for (i, field) in self.fields.iter_mut().enumerate() {
+            field.metric.fit(&Fields::column(records, i));
+        }
+    }
+
+    // Values of field `i` across all records
+    fn column<'a>(records: &[&[&'a str]], i: usize) -> Vec<&'a str> {
+        records
+            .iter()
+            .map(|r| r.get(i).copied().unwrap_or_default())
+            .collect()
+    }
+}
+
+impl Metric for Fields {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.is_similar_fields(&self.split(a), &self.split(b))
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.distance_fields(&self.split(a), &self.split(b))
+    }
+
+    fn validate(&self, inputs: &[&str]) -> Result<(), ValueError> {
+        let records: Vec<Vec<&str>> = inputs.iter().map(|s| self.split(s)).collect();
+        let records: Vec<&[&str]> = records.iter().map(Vec::as_slice).collect();
+        self.validate_records(&records)
+    }
+
+    fn fit(&mut self, inputs: &[&str]) {
+        let records: Vec<Vec<&str>> = inputs.iter().map(|s| self.split(s)).collect();
+        let records: Vec<&[&str]> = records.iter().map(Vec::as_slice).collect();
+        self.fit_records(&records)
+    }
+}
+
+/// Pre-split records, clustered with
+/// [`cluster_sequences_with_metric`](crate::cluster_sequences_with_metric).
+impl Metric<[&str]> for Fields {
+    fn is_similar(&self, a: &[&str], b: &[&str]) -> bool {
+        self.is_similar_fields(a, b)
+    }
+
+    fn distance(&self, a: &[&str], b: &[&str]) -> f64 {
+        self.distance_fields(a, b)
+    }
+
+    fn validate(&self, inputs: &[&[&str]]) -> Result<(), ValueError> {
+        self.validate_records(inputs)
+    }
+
+    fn fit(&mut self, inputs: &[&[&str]]) {
+        self.fit_records(inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Combination, Fields};
+    use crate::metric::{Hamming, Levenshtein, Metric, TfIdfCosine};
+    use crate::ValueError;
+
+    fn exact() -> Levenshtein {
+        Levenshtein::new(0.0).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_empty_delimiter() {
+        assert!(matches!(Fields::new(""), Err(ValueError::InvalidDelimiter)));
+    }
+
+    #[test]
+    fn with_weighted_field_rejects_invalid_weight() {
+        for weight in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            let result = Fields::new(",")
+                .unwrap()
+                .with_weighted_field(exact(), weight);
+            assert!(matches!(result, Err(ValueError::InvalidWeight)));
+        }
+    }
+
+    #[test]
+    fn with_combination_rejects_invalid_fraction() {
+        let result = Fields::new(",")
+            .unwrap()
+            .with_combination(Combination::WeightedVote(1.5));
+        assert!(matches!(result, Err(ValueError::InvalidFraction)));
+    }
+
+    #[test]
+    fn fields_split_and_trimmed() {
+        let metric = Fields::new("|")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact());
+        assert!(metric.is_similar("a | b", "a|b"));
+        assert!(!metric.is_similar("a | b", "a | c"));
+    }
+
+    #[test]
+    fn all_combination_applied() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact());
+        assert!(metric.is_similar("a,b", "a,b"));
+        assert!(!metric.is_similar("a,b", "a,c"));
+    }
+
+    #[test]
+    fn any_combination_applied() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact())
+            .with_combination(Combination::Any)
+            .unwrap();
+        assert!(metric.is_similar("a,b", "a,c"));
+        assert!(!metric.is_similar("a,b", "c,d"));
+    }
+
+    #[test]
+    fn weighted_vote_combination_applied() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_weighted_field(exact(), 3.0)
+            .unwrap()
+            .with_field(exact())
+            .with_combination(Combination::WeightedVote(0.75))
+            .unwrap();
+        assert!(metric.is_similar("a,b", "a,c"));
+        assert!(!metric.is_similar("a,b", "c,b"));
+    }
+
+    #[test]
+    fn pre_split_fields_compared() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact());
+        assert!(metric.is_similar_fields(&["a,b", "c"], &["a,b", "c"]));
+        assert!(!metric.is_similar_fields(&["a"], &["a", "b"]));
+    }
+
+    #[test]
+    fn distance_weights_dissimilar_fields() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_weighted_field(exact(), 3.0)
+            .unwrap()
+            .with_field(exact());
+        assert_eq!(metric.distance("a,b", "a,b"), 0.0);
+        assert_eq!(metric.distance("a,b", "a,c"), 0.25);
+        assert_eq!(metric.distance_fields(&["a", "b"], &["c", "b"]), 0.75);
+    }
+
+    #[test]
+    fn pre_split_records_validated() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact());
+        let records: [&[&str]; 2] = [&["a", "b"], &["c"]];
+        assert_eq!(
+            metric.validate(&records),
+            Err(ValueError::FieldCountMismatch)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_field_count_mismatch() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(exact());
+        assert_eq!(metric.validate(&["a,b", "c,d"]), Ok(()));
+        assert_eq!(
+            metric.validate(&["a,b", "c"]),
+            Err(ValueError::FieldCountMismatch)
+        );
+    }
+
+    #[test]
+    fn validate_applied_per_field() {
+        let metric = Fields::new(",")
+            .unwrap()
+            .with_field(exact())
+            .with_field(Hamming::new(0.5).unwrap());
+        assert_eq!(metric.validate(&["a,bb", "abc,cd"]), Ok(()));
+        assert_eq!(
+            metric.validate(&["a,bb", "a,c"]),
+            Err(ValueError::UnequalLengths)
+        );
+    }
+
+    #[test]
+    fn fit_applied_per_field() {
+        let mut metric = Fields::new("|")
+            .unwrap()
+            .with_field(TfIdfCosine::new(0.5).unwrap());
+        metric.fit(&["acme inc", "widgets inc", "gadgets inc"]);
+        assert!(!metric.is_similar("acme inc", "widgets inc"));
+    }
+}
@@ -0,0 +1,300 @@
+use caseless::{CaseFold, Caseless};
+use std::cell::RefCell;
+use std::hash::{Hash, Hasher};
+use std::str::Chars;
+use std::thread::LocalKey;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Case sensitivity of edit distance metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Characters of different case are different.
+    #[default]
+    Sensitive,
+    /// ASCII letters are compared regardless of case. Other characters are
+    /// compared as they are.
+    InsensitiveAscii,
+    /// Characters are compared after Unicode default case folding, so "Straße"
+    /// and "STRASSE" are equal.
+    Insensitive,
+}
+
+/// Handling of whitespace by edit distance metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Whitespace {
+    /// Whitespace characters are compared like any other character.
+    #[default]
+    Significant,
+    /// Leading and trailing whitespace is ignored and each run of whitespace
+    /// is compared as a single space.
+    Collapsed,
+    /// Whitespace is ignored entirely, so "New York" and "NewYork" are equal.
+    Ignored,
+}
+
+// Case and whitespace handling applied to each symbol as strings are split.
+// Folded symbols are written to buffers kept by each thread, so comparisons
+// only allocate when a string is longer than any folded before on the thread
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Folding {
+    pub case: Case,
+    pub whitespace: Whitespace,
+}
+
+impl Folding {
+    pub fn is_identity(&self) -> bool {
+        *self == Folding::default()
+    }
+
+    pub fn chars<'a>(&self, s: &'a str) -> impl Iterator<Item = char> + 'a {
+        Spaced::new(CaseChars::new(s, self.case), self.whitespace, ' ', |c| {
+            c.is_whitespace()
+        })
+    }
+
+    pub fn graphemes<'a>(&self, s: &'a str) -> impl Iterator<Item = Grapheme<'a>> + 'a {
+        let case = self.case;
+        Spaced::new(s.graphemes(true), self.whitespace, " ", |g| {
+            g.chars().all(char::is_whitespace)
+        })
+        .map(move |grapheme| Grapheme { grapheme, case })
+    }
+
+    // Fold both strings into chars and pass them to `f`
+    pub fn with_chars<R>(&self, a: &str, b: &str, f: impl FnOnce(&[char], &[char]) -> R) -> R {
+        with_buffers(
+            &CHARS,
+            |x, y| {
+                x.extend(self.chars(a));
+                y.extend(self.chars(b));
+            },
+            f,
+        )
+    }
+}
+
+pub type Buffers<T> = RefCell<(Vec<T>, Vec<T>)>;
+
+thread_local! {
+    static CHARS: Buffers<char> = const { RefCell::new((Vec::new(), Vec::new())) };
+}
+
+// Fill the thread's buffers with `fill` and pass their contents to `f`. Fresh
+// buffers are used if the thread's are already in use further up the stack.
+pub fn with_buffers<T: 'static, R>(
+    buffers: &'static LocalKey<Buffers<T>>,
+    fill: impl FnOnce(&mut Vec<T>, &mut Vec<T>),
+    f: impl FnOnce(&[T], &[T]) -> R,
+) -> R {
+    buffers.with(|buffers| match buffers.try_borrow_mut() {
+        Ok(mut buffers) => {
+            let (a, b) = &mut *buffers;
+            a.clear();
+            b.clear();
+            fill(a, b);
+            f(a, b)
+        }
+        Err(_) => {
+            let (mut a, mut b) = (Vec::new(), Vec::new());
+            fill(&mut a, &mut b);
+            f(&a, &b)
+        }
+    })
+}
+
+enum CaseChars<'a> {
+    Sensitive(Chars<'a>),
+    InsensitiveAscii(Chars<'a>),
+    Insensitive(CaseFold<Chars<'a>>),
+}
+
+impl<'a> CaseChars<'a> {
+    fn new(s: &'a str, case: Case) -> CaseChars<'a> {
+        match case {
+            Case::Sensitive => CaseChars::Sensitive(s.chars()),
+            Case::InsensitiveAscii => CaseChars::InsensitiveAscii(s.chars()),
+            Case::Insensitive => CaseChars::Insensitive(s.chars().default_case_fold()),
+        }
+    }
+}
+
+impl Iterator for CaseChars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        match self {
+            CaseChars::Sensitive(chars) => chars.next(),
+            CaseChars::InsensitiveAscii(chars) => chars.next().map(|c| c.to_ascii_lowercase()),
+            CaseChars::Insensitive(chars) => chars.next(),
+        }
+    }
+}
+
+// Grapheme compared and hashed by its case folded chars
+#[derive(Clone, Copy, Debug)]
+pub struct Grapheme<'a> {
+    grapheme: &'a str,
+    case: Case,
+}
+
+impl Grapheme<'_> {
+    fn chars(&self) -> CaseChars<'_> {
+        CaseChars::new(self.grapheme, self.case)
+    }
+}
+
+impl PartialEq for Grapheme<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl Eq for Grapheme<'_> {}
+
+impl Hash for Grapheme<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for c in self.chars() {
+            c.hash(state);
+        }
+    }
+}
+
+// Applies the whitespace handling to a sequence of symbols. A collapsed run
+// of whitespace is only emitted once the next symbol is known not to be
+// trailing whitespace.
+struct Spaced<I: Iterator> {
+    symbols: I,
+    whitespace: Whitespace,
+    space: I::Item,
+    is_space: fn(&I::Item) -> bool,
+    started: bool,
+    pending_space: bool,
+    buffered: Option<I::Item>,
+}
+
+impl<I: Iterator> Spaced<I> {
+    fn new(
+        symbols: I,
+        whitespace: Whitespace,
+        space: I::Item,
+        is_space: fn(&I::Item) -> bool,
+    ) -> Spaced<I> {
+        Spaced {
+            symbols,
+            whitespace,
+            space,
+            is_space,
+            started: false,
+            pending_space: false,
+            buffered: None,
+        }
+    }
+}
+
+impl<I> Iterator for Spaced<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        if let Some(symbol) = self.buffered.take() {
+            return Some(symbol);
+        }
+        loop {
+            let symbol = self.symbols.next()?;
+            if self.whitespace == Whitespace::Significant || !(self.is_space)(&symbol) {
+                self.started = true;
+                if self.pending_space {
+                    self.pending_space = false;
+                    self.buffered = Some(symbol);
+                    return Some(self.space.clone());
+                }
+                return Some(symbol);
+            }
+            self.pending_space = self.started && self.whitespace == Whitespace::Collapsed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{with_buffers, Buffers, Case, Folding, Whitespace};
+    use std::cell::RefCell;
+
+    fn folded(s: &str, case: Case, whitespace: Whitespace) -> String {
+        Folding { case, whitespace }.chars(s).collect()
+    }
+
+    #[test]
+    fn identity_by_default() {
+        assert!(Folding::default().is_identity());
+        let s = " Straße\tNo. 5 ";
+        assert_eq!(folded(s, Case::Sensitive, Whitespace::Significant), s);
+    }
+
+    #[test]
+    fn ascii_case_folded() {
+        let result = folded("ÄBc", Case::InsensitiveAscii, Whitespace::Significant);
+        assert_eq!(result, "Äbc");
+    }
+
+    #[test]
+    fn unicode_case_folded() {
+        let result = folded("ÄBc Straße", Case::Insensitive, Whitespace::Significant);
+        assert_eq!(result, "äbc strasse");
+    }
+
+    #[test]
+    fn whitespace_collapsed() {
+        let result = folded("  a \t b\n\nc  ", Case::Sensitive, Whitespace::Collapsed);
+        assert_eq!(result, "a b c");
+    }
+
+    #[test]
+    fn whitespace_ignored() {
+        let result = folded("  a \t b\n\nc  ", Case::Sensitive, Whitespace::Ignored);
+        assert_eq!(result, "abc");
+    }
+
+    #[test]
+    fn graphemes_compared_by_folded_chars() {
+        let folding = Folding {
+            case: Case::Insensitive,
+            whitespace: Whitespace::Collapsed,
+        };
+        let a: Vec<_> = folding.graphemes("É  x").collect();
+        let b: Vec<_> = folding.graphemes("é x ").collect();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 3);
+    }
+
+    #[test]
+    fn chars_folded_into_buffers() {
+        let folding = Folding {
+            case: Case::InsensitiveAscii,
+            whitespace: Whitespace::Ignored,
+        };
+        folding.with_chars("A b", "ab", |a, b| assert_eq!(a, b));
+        folding.with_chars("xy", "", |a, b| {
+            assert_eq!(a, ['x', 'y']);
+            assert!(b.is_empty());
+        });
+    }
+
+    #[test]
+    fn nested_use_gets_fresh_buffers() {
+        thread_local! {
+            static BUFFERS: Buffers<u8> = const { RefCell::new((Vec::new(), Vec::new())) };
+        }
+        let fill = |a: &mut Vec<u8>, b: &mut Vec<u8>| {
+            a.push(1);
+            b.push(2);
+        };
+        let inner = with_buffers(&BUFFERS, fill, |a, b| {
+            with_buffers(&BUFFERS, fill, |c, d| (a, b) == (c, d))
+        });
+        assert!(inner);
+    }
+}
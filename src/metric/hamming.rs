@@ -0,0 +1,268 @@
+use super::config::{edit_config_builders, EditConfig};
+use super::kernel::{hamming_distance, hamming_distance_packed};
+use super::Metric;
+use crate::ValueError;
+use std::any::TypeId;
+
+/// Handling of strings of different lengths by [`Hamming`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LengthPolicy {
+    /// Reject inputs of different lengths with [`ValueError::UnequalLengths`]
+    /// before clustering starts.
+    #[default]
+    Error,
+    /// Treat strings of different lengths as dissimilar.
+    Dissimilar,
+}
+
+/// Hamming distance thresholded on a fraction of the strings' length.
+///
+/// Strings are similar if they have the same length and differ in at most
+/// `max_edit_frac` of their chars (rounded down). Only substitutions are
+/// counted, which suits fixed-length codes such as barcodes, hashes and
+/// k-mers. Byte sequences, and ASCII strings unless case or whitespace
+/// handling is configured, are compared eight bytes at a time.
+///
+/// Lengths are measured after whitespace is collapsed or ignored, and the
+/// distance between strings of different lengths is infinite.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hamming {
+    config: EditConfig,
+    length_policy: LengthPolicy,
+}
+
+impl Hamming {
+    /// Create a new metric rejecting inputs of different lengths.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(max_edit_frac: f32) -> Result<Hamming, ValueError> {
+        Ok(Hamming {
+            config: EditConfig::new(max_edit_frac)?,
+            length_policy: LengthPolicy::Error,
+        })
+    }
+
+    /// Set the handling of strings of different lengths.
+    pub fn with_length_policy(mut self, length_policy: LengthPolicy) -> Hamming {
+        self.length_policy = length_policy;
+        self
+    }
+
+    fn is_packed(&self, a: &str, b: &str) -> bool {
+        self.config.folding.is_identity() && a.is_ascii() && b.is_ascii()
+    }
+}
+
+edit_config_builders!(Hamming, "number of substitutions");
+
+impl Metric for Hamming {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        if self.is_packed(a, b) {
+            if a.len() != b.len() {
+                return false;
+            }
+            let max_edit = self.config.limit.max_edits(a.len(), b.len());
+            return hamming_distance_packed(a.as_bytes(), b.as_bytes(), max_edit) <= max_edit;
+        }
+
+        self.config
+            .folding
+            .with_chars(a, b, |a, b| self.is_similar(a, b))
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        if self.is_packed(a, b) {
+            if a.len() != b.len() {
+                return f64::INFINITY;
+            }
+            return hamming_distance_packed(a.as_bytes(), b.as_bytes(), a.len()) as f64;
+        }
+
+        self.config
+            .folding
+            .with_chars(a, b, |a, b| self.distance(a, b))
+    }
+
+    fn validate(&self, inputs: &[&str]) -> Result<(), ValueError> {
+        let lengths = inputs.iter().map(|s| self.config.folding.chars(s).count());
+        check_lengths(self.length_policy, lengths)
+    }
+}
+
+impl<T: Eq + Sync + 'static> Metric<[T]> for Hamming {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        if a.len() != b.len() {
+            return false;
+        }
+        let max_edit = self.config.limit.max_edits(a.len(), b.len());
+        distance(a, b, max_edit) <= max_edit
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        if a.len() != b.len() {
+            return f64::INFINITY;
+        }
+        distance(a, b, a.len()) as f64
+    }
+
+    fn validate(&self, inputs: &[&[T]]) -> Result<(), ValueError> {
+        check_lengths(self.length_policy, inputs.iter().map(|s| s.len()))
+    }
+}
+
+// Hamming distance bounded by `max`, comparing bytes eight at a time
+fn distance<T: Eq + 'static>(a: &[T], b: &[T], max: usize) -> usize {
+    match (as_bytes(a), as_bytes(b)) {
+        (Some(a), Some(b)) => hamming_distance_packed(a, b, max),
+        _ => hamming_distance(a, b, max),
+    }
+}
+
+// The elements of `s` as bytes, if they are bytes
+fn as_bytes<T: 'static>(s: &[T]) -> Option<&[u8]> {
+    if TypeId::of::<T>() != TypeId::of::<u8>() {
+        return None;
+    }
+    // SAFETY: `T` is `u8`, so `s` is a valid slice of `s.len()` bytes
+    Some(unsafe { std::slice::from_raw_parts(s.as_ptr().cast::<u8>(), s.len()) })
+}
+
+fn check_lengths<I>(length_policy: LengthPolicy, mut lengths: I) -> Result<(), ValueError>
+where
+    I: Iterator<Item = usize>,
+{
+    if length_policy == LengthPolicy::Dissimilar {
+        return Ok(());
+    }
+    let first = lengths.next();
+    if lengths.any(|len| Some(len) != first) {
+        return Err(ValueError::UnequalLengths);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{as_bytes, Hamming, LengthPolicy};
+    use crate::metric::{Case, EditThreshold, Metric, Whitespace};
+    use crate::ValueError;
+
+    #[test]
+    fn new_rejects_invalid_fraction() {
+        assert_eq!(Hamming::new(1.5), Err(ValueError::InvalidFraction));
+    }
+
+    #[test]
+    fn accepts_within_max_edit() {
+        let metric = Hamming::new(0.25).unwrap();
+        assert!(metric.is_similar("ACGTACGT", "ACGAACGA"));
+        assert!(!metric.is_similar("ACGTACGT", "TCGAACGA"));
+    }
+
+    #[test]
+    fn rejects_shifted_strings() {
+        let metric = Hamming::new(0.5).unwrap();
+        assert!(!metric.is_similar("abcdef", "bcdefa"));
+    }
+
+    #[test]
+    fn rejects_unequal_lengths() {
+        let metric = Hamming::new(1.0).unwrap();
+        assert!(!metric.is_similar("abc", "abcd"));
+        assert!(!metric.is_similar("日本", "日本語"));
+    }
+
+    #[test]
+    fn measures_multi_byte_strings_in_chars() {
+        let metric = Hamming::new(0.5).unwrap();
+        assert!(metric.is_similar("日本", "日中"));
+        assert!(metric.is_similar("café", "cafe"));
+        assert!(!metric.is_similar("日本", "中国"));
+    }
+
+    #[test]
+    fn threshold_policy_applied() {
+        let metric = Hamming::new(0.0)
+            .unwrap()
+            .with_threshold(EditThreshold::Absolute(2))
+            .unwrap();
+        assert!(metric.is_similar("0123456789abcdef", "0123456789abcdXY"));
+        let metric = metric.with_edit_bounds(0, 1).unwrap();
+        assert!(!metric.is_similar("0123456789abcdef", "0123456789abcdXY"));
+    }
+
+    #[test]
+    fn compares_sequences() {
+        let metric = Hamming::new(0.25).unwrap();
+        let (a, b): (&[u8], &[u8]) = (b"ACGTACGT", b"ACGAACGA");
+        assert!(metric.is_similar(a, b));
+        let (a, b): (&[u32], &[u32]) = (&[1, 2], &[1, 2, 3]);
+        assert!(!metric.is_similar(a, b));
+    }
+
+    #[test]
+    fn case_insensitivity_applied() {
+        let metric = Hamming::new(0.0).unwrap();
+        assert!(!metric.is_similar("acgt", "ACGT"));
+        let metric = metric.with_case(Case::InsensitiveAscii);
+        assert!(metric.is_similar("acgt", "ACGT"));
+        assert_eq!(metric.distance("acgt", "ACGA"), 1.0);
+    }
+
+    #[test]
+    fn whitespace_handling_applied() {
+        let metric = Hamming::new(0.0)
+            .unwrap()
+            .with_whitespace(Whitespace::Ignored);
+        assert!(metric.is_similar("AB CD", "ABCD"));
+        assert_eq!(metric.validate(&["AB CD", "ABCD"]), Ok(()));
+    }
+
+    #[test]
+    fn distance_counts_substitutions() {
+        let metric = Hamming::new(0.0).unwrap();
+        assert_eq!(metric.distance("ACGTACGTAC", "TCGAACGTAA"), 3.0);
+        assert_eq!(metric.distance("日本", "日中"), 1.0);
+        assert_eq!(metric.distance("abc", "abcd"), f64::INFINITY);
+        let (a, b): (&[u32], &[u32]) = (&[1, 2, 3], &[1, 5, 3]);
+        assert_eq!(metric.distance(a, b), 1.0);
+    }
+
+    #[test]
+    fn packs_byte_sequences() {
+        let metric = Hamming::new(0.0).unwrap();
+        let a: &[u8] = b"ACGTACGTACGTACGTAC";
+        let b: &[u8] = b"ACGAACGTACGTACCTAG";
+        assert_eq!(as_bytes(a), Some(a));
+        assert_eq!(as_bytes::<u32>(&[1, 2]), None);
+        assert_eq!(metric.distance(a, b), 3.0);
+        let metric = metric.with_edit_bounds(3, 3).unwrap();
+        assert!(metric.is_similar(a, b));
+    }
+
+    #[test]
+    fn validate_rejects_unequal_sequence_lengths() {
+        let metric = Hamming::new(0.5).unwrap();
+        let inputs: [&[u8]; 2] = [b"ab", b"abc"];
+        assert_eq!(metric.validate(&inputs), Err(ValueError::UnequalLengths));
+    }
+
+    #[test]
+    fn validate_rejects_unequal_lengths() {
+        let metric = Hamming::new(0.5).unwrap();
+        assert_eq!(metric.validate(&["ab", "日本"]), Ok(()));
+        assert_eq!(
+            metric.validate(&["ab", "abc"]),
+            Err(ValueError::UnequalLengths)
+        );
+    }
+
+    #[test]
+    fn validate_accepts_unequal_lengths_if_dissimilar() {
+        let metric = Hamming::new(0.5)
+            .unwrap()
+            .with_length_policy(LengthPolicy::Dissimilar);
+        assert_eq!(metric.validate(&["ab", "abc"]), Ok(()));
+    }
+}
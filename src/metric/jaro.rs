@@ -0,0 +1,182 @@
+use super::kernel::jaro_similarity;
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+// Winkler's original parameters
+const DEFAULT_PREFIX_SCALE: f32 = 0.1;
+const DEFAULT_BOOST_THRESHOLD: f32 = 0.7;
+const MAX_PREFIX_SCALE: f32 = 0.25;
+const MAX_PREFIX_LEN: usize = 4;
+
+/// Jaro-Winkler similarity thresholded on a minimum similarity.
+///
+/// Strings are similar if their Jaro-Winkler similarity is at least
+/// `threshold`. Jaro similarities above the boost threshold are increased in
+/// proportion to the length of the common prefix (up to four characters),
+/// which suits short strings such as person and company names.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JaroWinkler {
+    threshold: f32,
+    prefix_scale: f32,
+    boost_threshold: f32,
+}
+
+impl JaroWinkler {
+    /// Create a new metric with a prefix scale of 0.1 and a boost threshold of 0.7.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<JaroWinkler, ValueError> {
+        check_fraction(threshold)?;
+        Ok(JaroWinkler {
+            threshold,
+            prefix_scale: DEFAULT_PREFIX_SCALE,
+            boost_threshold: DEFAULT_BOOST_THRESHOLD,
+        })
+    }
+
+    /// Set the weight given to the common prefix.
+    ///
+    /// Returns [`ValueError::InvalidPrefixScale`] if `prefix_scale` is outside of
+    /// the closed interval \[0,0.25\]. Larger values could produce similarities
+    /// above one.
+    pub fn with_prefix_scale(mut self, prefix_scale: f32) -> Result<JaroWinkler, ValueError> {
+        if !(0.0..=MAX_PREFIX_SCALE).contains(&prefix_scale) {
+            return Err(ValueError::InvalidPrefixScale);
+        }
+        self.prefix_scale = prefix_scale;
+        Ok(self)
+    }
+
+    /// Set the Jaro similarity above which the prefix boost is applied.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `boost_threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn with_boost_threshold(mut self, boost_threshold: f32) -> Result<JaroWinkler, ValueError> {
+        check_fraction(boost_threshold)?;
+        self.boost_threshold = boost_threshold;
+        Ok(self)
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        self.sequence_similarity(&a, &b)
+    }
+
+    fn sequence_similarity<T: Eq>(&self, a: &[T], b: &[T]) -> f64 {
+        let jaro = jaro_similarity(a, b);
+
+        if jaro <= self.boost_threshold as f64 {
+            return jaro;
+        }
+
+        let prefix_len = a
+            .iter()
+            .zip(b.iter())
+            .take(MAX_PREFIX_LEN)
+            .take_while(|(x, y)| x == y)
+            .count();
+        jaro + prefix_len as f64 * self.prefix_scale as f64 * (1.0 - jaro)
+    }
+}
+
+impl Metric for JaroWinkler {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for JaroWinkler {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        self.sequence_similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        1.0 - self.sequence_similarity(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JaroWinkler;
+    use crate::metric::Metric;
+    use crate::ValueError;
+
+    mod jaro_winkler {
+        use super::{JaroWinkler, Metric, ValueError};
+        use crate::metric::testing::assert_close_to_reference;
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(JaroWinkler::new(1.1), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn prefix_scale_rejected_above_quarter() {
+            let result = JaroWinkler::new(0.9).unwrap().with_prefix_scale(0.3);
+            assert_eq!(result, Err(ValueError::InvalidPrefixScale));
+        }
+
+        #[test]
+        fn boost_threshold_rejects_invalid_fraction() {
+            let result = JaroWinkler::new(0.9).unwrap().with_boost_threshold(-0.1);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn reference_values_correct() {
+            let metric = JaroWinkler::new(0.9).unwrap();
+            assert_close_to_reference(metric.similarity("martha", "marhta"), 0.961);
+            assert_close_to_reference(metric.similarity("dixon", "dicksonx"), 0.813);
+            assert_close_to_reference(metric.similarity("dwayne", "duane"), 0.840);
+        }
+
+        #[test]
+        fn prefix_boost_skipped_below_boost_threshold() {
+            let metric = JaroWinkler::new(0.9)
+                .unwrap()
+                .with_boost_threshold(0.95)
+                .unwrap();
+            assert_close_to_reference(metric.similarity("martha", "marhta"), 0.944);
+        }
+
+        #[test]
+        fn prefix_scale_applied() {
+            let metric = JaroWinkler::new(0.9)
+                .unwrap()
+                .with_prefix_scale(0.0)
+                .unwrap();
+            assert_close_to_reference(metric.similarity("martha", "marhta"), 0.944);
+        }
+
+        #[test]
+        fn threshold_applied() {
+            let metric = JaroWinkler::new(0.95).unwrap();
+            assert!(metric.is_similar("martha", "marhta"));
+            assert!(!metric.is_similar("dixon", "dicksonx"));
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = JaroWinkler::new(0.95).unwrap();
+            let (a, b): (&[u8], &[u8]) = (b"martha", b"marhta");
+            assert!(metric.is_similar(a, b));
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3], &[4, 5, 6]);
+            assert!(!metric.is_similar(a, b));
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = JaroWinkler::new(0.9).unwrap();
+            assert_close_to_reference(metric.distance("martha", "marhta"), 0.039);
+            let (a, b): (&[u8], &[u8]) = (b"martha", b"marhta");
+            assert_close_to_reference(metric.distance(a, b), 0.039);
+        }
+    }
+}
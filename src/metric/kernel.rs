@@ -0,0 +1,767 @@
+mod bit_parallel;
+
+pub use bit_parallel::levenshtein_distance_bit_parallel;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Levenshtein distance, bounded by `max`. Returns `max + 1` as soon as the
+/// distance is known to exceed `max`.
+///
+/// Only cells within `max` of the diagonal can lie on a path of cost at most
+/// `max` (Ukkonen), so each row is restricted to a band of width `2 * max + 1`
+/// and computation stops once every cell in the band exceeds `max`.
+pub fn levenshtein_distance_banded<T: Eq>(a: &[T], b: &[T], max: usize) -> usize {
+    let exceeded = max + 1;
+
+    if a.len().abs_diff(b.len()) > max {
+        return exceeded;
+    }
+    if a.is_empty() || b.is_empty() {
+        return a.len().max(b.len());
+    }
+
+    // Cells outside of the band are treated as exceeding the bound
+    let mut prev: Vec<usize> = (0..=b.len()).map(|j| j.min(exceeded)).collect();
+    let mut curr = vec![exceeded; b.len() + 1];
+
+    for i in 1..=a.len() {
+        let lo = i.saturating_sub(max).max(1);
+        let hi = (i + max).min(b.len());
+
+        curr[lo - 1] = if lo == 1 { i.min(exceeded) } else { exceeded };
+        let mut row_min = curr[lo - 1];
+
+        for j in lo..=hi {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            curr[j] = (prev[j] + 1)
+                .min(curr[j - 1] + 1)
+                .min(prev[j - 1] + cost)
+                .min(exceeded);
+            row_min = row_min.min(curr[j]);
+        }
+        if hi < b.len() {
+            curr[hi + 1] = exceeded;
+        }
+
+        // Distances never decrease from one row to the next
+        if row_min > max {
+            return exceeded;
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Levenshtein distance with custom operation costs. Insertions add symbols of
+/// `b`, deletions remove symbols of `a`.
+pub fn weighted_levenshtein_distance<T, F>(
+    a: &[T],
+    b: &[T],
+    insert: f64,
+    delete: f64,
+    substitute: F,
+) -> f64
+where
+    T: Eq,
+    F: Fn(&T, &T) -> f64,
+{
+    let mut prev: Vec<f64> = (0..=b.len()).map(|j| j as f64 * insert).collect();
+    let mut curr = vec![0.0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i as f64 * delete;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] {
+                0.0
+            } else {
+                substitute(&a[i - 1], &b[j - 1])
+            };
+            curr[j] = (prev[j] + delete)
+                .min(curr[j - 1] + insert)
+                .min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Optimal string alignment distance. Adjacent transpositions cost a single
+/// edit, but no substring may be edited more than once.
+pub fn osa_distance<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut prev_prev = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                curr[j] = curr[j].min(prev_prev[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Unrestricted Damerau-Levenshtein distance (Lowrance-Wagner). Adjacent
+/// transpositions cost a single edit and substrings may be edited repeatedly.
+pub fn damerau_levenshtein_distance<T: Eq + Hash>(a: &[T], b: &[T]) -> usize {
+    let max_dist = a.len() + b.len();
+    let width = b.len() + 2;
+    let mut d = vec![0; (a.len() + 2) * width];
+
+    d[0] = max_dist;
+    for i in 0..=a.len() {
+        d[(i + 1) * width] = max_dist;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        d[j + 1] = max_dist;
+        d[width + j + 1] = j;
+    }
+
+    // Last row in which each symbol of `a` was seen
+    let mut last_row: HashMap<&T, usize> = HashMap::new();
+
+    for i in 1..=a.len() {
+        // Last column in the current row with a matching symbol
+        let mut last_match_col = 0;
+
+        for j in 1..=b.len() {
+            let i1 = *last_row.get(&b[j - 1]).unwrap_or(&0);
+            let j1 = last_match_col;
+
+            let cost = if a[i - 1] == b[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+
+            let substitution = d[i * width + j] + cost;
+            let insertion = d[(i + 1) * width + j] + 1;
+            let deletion = d[i * width + j + 1] + 1;
+            let transposition = d[i1 * width + j1] + (i - i1 - 1) + 1 + (j - j1 - 1);
+
+            d[(i + 1) * width + j + 1] =
+                substitution.min(insertion).min(deletion).min(transposition);
+        }
+        last_row.insert(&a[i - 1], i);
+    }
+    d[(a.len() + 1) * width + b.len() + 1]
+}
+
+/// Jaro similarity in \[0,1\].
+pub fn jaro_similarity<T: Eq>(a: &[T], b: &[T]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    // Symbols only match if they are no further apart than this
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+
+    let mut a_matched = vec![false; a.len()];
+    let mut b_matched = vec![false; b.len()];
+    let mut matches = 0;
+
+    for (i, x) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+
+        for j in start..end {
+            if !b_matched[j] && *x == b[j] {
+                a_matched[i] = true;
+                b_matched[j] = true;
+                matches += 1;
+                break;
+            }
+        }
+    }
+
+    if matches == 0 {
+        return 0.0;
+    }
+
+    // Count matched symbols that appear in a different order
+    let mut half_transpositions = 0;
+    let mut k = 0;
+    for (i, x) in a.iter().enumerate() {
+        if !a_matched[i] {
+            continue;
+        }
+        while !b_matched[k] {
+            k += 1;
+        }
+        if *x != b[k] {
+            half_transpositions += 1;
+        }
+        k += 1;
+    }
+
+    let m = matches as f64;
+    let t = (half_transpositions / 2) as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - t) / m) / 3.0
+}
+
+/// Length of the longest common subsequence.
+pub fn lcs_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut prev = vec![0; b.len() + 1];
+    let mut curr = vec![0; b.len() + 1];
+
+    for x in a {
+        for (j, y) in b.iter().enumerate() {
+            curr[j + 1] = if x == y {
+                prev[j] + 1
+            } else {
+                prev[j + 1].max(curr[j])
+            };
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    prev[b.len()]
+}
+
+/// Hamming distance between sequences of equal length, bounded by `max`.
+/// Returns `max + 1` as soon as the distance is known to exceed `max`.
+pub fn hamming_distance<T: Eq>(a: &[T], b: &[T], max: usize) -> usize {
+    debug_assert_eq!(a.len(), b.len());
+    let mut dist = 0;
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            dist += 1;
+            if dist > max {
+                break;
+            }
+        }
+    }
+    dist
+}
+
+/// Hamming distance between byte strings of equal length, bounded by `max`.
+///
+/// Compares eight bytes per machine word: the XOR of two words is folded so
+/// that the lowest bit of each byte is set if the bytes differ, and the set
+/// bits are counted.
+pub fn hamming_distance_packed(a: &[u8], b: &[u8], max: usize) -> usize {
+    const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+    debug_assert_eq!(a.len(), b.len());
+
+    let chunks_a = a.chunks_exact(8);
+    let chunks_b = b.chunks_exact(8);
+    let (rem_a, rem_b) = (chunks_a.remainder(), chunks_b.remainder());
+
+    let mut dist = 0;
+    for (x, y) in chunks_a.zip(chunks_b) {
+        let x = u64::from_ne_bytes(x.try_into().expect("chunk of eight bytes"));
+        let y = u64::from_ne_bytes(y.try_into().expect("chunk of eight bytes"));
+        let mut diff = x ^ y;
+        diff |= diff >> 4;
+        diff |= diff >> 2;
+        diff |= diff >> 1;
+        dist += (diff & LOW_BITS).count_ones() as usize;
+        if dist > max {
+            return dist;
+        }
+    }
+    dist + hamming_distance(rem_a, rem_b, max.saturating_sub(dist))
+}
+
+/// Length of the longest common substring.
+pub fn longest_common_substring_length<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut prev = vec![0; b.len() + 1];
+    let mut curr = vec![0; b.len() + 1];
+    let mut longest = 0;
+
+    for x in a {
+        for (j, y) in b.iter().enumerate() {
+            curr[j + 1] = if x == y { prev[j] + 1 } else { 0 };
+            longest = longest.max(curr[j + 1]);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+    longest
+}
+
+/// Score of the best global alignment (Needleman-Wunsch) with affine gap
+/// penalties: a gap of `k` symbols scores `-(open + (k - 1) * extend)`.
+pub fn global_alignment_score<T, F>(a: &[T], b: &[T], open: f64, extend: f64, score: F) -> f64
+where
+    F: Fn(&T, &T) -> f64,
+{
+    affine_alignment_score(a, b, open, extend, score, false)
+}
+
+/// Score of the best local alignment (Smith-Waterman) with affine gap
+/// penalties, i.e. of the best scoring pair of substrings. Never negative.
+pub fn local_alignment_score<T, F>(a: &[T], b: &[T], open: f64, extend: f64, score: F) -> f64
+where
+    F: Fn(&T, &T) -> f64,
+{
+    affine_alignment_score(a, b, open, extend, score, true)
+}
+
+// Gotoh's recurrences in linear space. `h` holds the best score of an alignment
+// ending in each cell, `x` of one ending in a gap in `b` and `y` of one ending
+// in a gap in `a`. Local alignments may restart at zero in any cell.
+fn affine_alignment_score<T, F>(
+    a: &[T],
+    b: &[T],
+    open: f64,
+    extend: f64,
+    score: F,
+    local: bool,
+) -> f64
+where
+    F: Fn(&T, &T) -> f64,
+{
+    let floor = if local { 0.0 } else { f64::NEG_INFINITY };
+    let leading_gap = |k: usize| {
+        if local || k == 0 {
+            0.0
+        } else {
+            -(open + (k - 1) as f64 * extend)
+        }
+    };
+
+    let mut h_prev: Vec<f64> = (0..=b.len()).map(leading_gap).collect();
+    let mut x_prev = vec![f64::NEG_INFINITY; b.len() + 1];
+    let mut h_curr = vec![0.0; b.len() + 1];
+    let mut x_curr = vec![f64::NEG_INFINITY; b.len() + 1];
+    let mut best = 0.0;
+
+    for (i, symbol_a) in a.iter().enumerate() {
+        h_curr[0] = leading_gap(i + 1);
+        x_curr[0] = h_curr[0];
+        let mut y = f64::NEG_INFINITY;
+
+        for (j, symbol_b) in b.iter().enumerate() {
+            let diagonal = h_prev[j] + score(symbol_a, symbol_b);
+            x_curr[j + 1] = (h_prev[j + 1] - open).max(x_prev[j + 1] - extend);
+            y = (h_curr[j] - open).max(y - extend);
+            h_curr[j + 1] = diagonal.max(x_curr[j + 1]).max(y).max(floor);
+            best = f64::max(best, h_curr[j + 1]);
+        }
+        std::mem::swap(&mut h_prev, &mut h_curr);
+        std::mem::swap(&mut x_prev, &mut x_curr);
+    }
+
+    if local {
+        best
+    } else {
+        h_prev[b.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        damerau_levenshtein_distance, global_alignment_score, hamming_distance,
+        hamming_distance_packed, jaro_similarity, lcs_length, levenshtein_distance_banded,
+        local_alignment_score, longest_common_substring_length, osa_distance,
+        weighted_levenshtein_distance,
+    };
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    mod levenshtein_distance_banded {
+        use super::{chars, levenshtein_distance_banded};
+        use edit_distance::edit_distance;
+
+        #[test]
+        fn distance_correct_within_bound() {
+            let result = levenshtein_distance_banded(&chars("kitten"), &chars("sitting"), 3);
+            assert_eq!(result, 3);
+        }
+
+        #[test]
+        fn transposition_costs_two_edits() {
+            let result = levenshtein_distance_banded(&chars("teh"), &chars("the"), 5);
+            assert_eq!(result, 2);
+        }
+
+        #[test]
+        fn exceeded_bound_reported() {
+            let result = levenshtein_distance_banded(&chars("kitten"), &chars("sitting"), 2);
+            assert_eq!(result, 3);
+            let result = levenshtein_distance_banded(&chars("abcdefgh"), &chars("stuvwxyz"), 1);
+            assert_eq!(result, 2);
+        }
+
+        #[test]
+        fn length_difference_exceeding_bound_rejected() {
+            let result = levenshtein_distance_banded(&chars("a"), &chars("abcd"), 2);
+            assert_eq!(result, 3);
+        }
+
+        #[test]
+        fn zero_bound_requires_equality() {
+            assert_eq!(
+                levenshtein_distance_banded(&chars("abc"), &chars("abc"), 0),
+                0
+            );
+            assert_eq!(
+                levenshtein_distance_banded(&chars("abc"), &chars("abd"), 0),
+                1
+            );
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(levenshtein_distance_banded(&chars(""), &chars("abc"), 3), 3);
+            assert_eq!(levenshtein_distance_banded(&chars("abc"), &chars(""), 3), 3);
+            assert_eq!(levenshtein_distance_banded(&chars(""), &chars(""), 0), 0);
+        }
+
+        #[test]
+        fn matches_unbounded_distance() {
+            let words = [
+                "", "a", "ab", "ba", "abc", "flaw", "lawn", "saturday", "sunday",
+            ];
+            for a in words {
+                for b in words {
+                    let expected = edit_distance(a, b);
+                    for max in 0..10 {
+                        let result = levenshtein_distance_banded(&chars(a), &chars(b), max);
+                        assert_eq!(result, expected.min(max + 1), "{} {} {}", a, b, max);
+                    }
+                }
+            }
+        }
+    }
+
+    mod weighted_levenshtein_distance {
+        use super::{chars, weighted_levenshtein_distance};
+
+        #[test]
+        fn unit_costs_match_levenshtein() {
+            let result = weighted_levenshtein_distance(
+                &chars("kitten"),
+                &chars("sitting"),
+                1.0,
+                1.0,
+                |_, _| 1.0,
+            );
+            assert_eq!(result, 3.0);
+        }
+
+        #[test]
+        fn operation_costs_applied() {
+            let result =
+                weighted_levenshtein_distance(&chars("ab"), &chars("abc"), 0.5, 2.0, |_, _| 1.0);
+            assert_eq!(result, 0.5);
+            let result =
+                weighted_levenshtein_distance(&chars("abc"), &chars("ab"), 0.5, 2.0, |_, _| 1.0);
+            assert_eq!(result, 2.0);
+        }
+
+        #[test]
+        fn substitution_cost_applied() {
+            let cost = |a: &char, b: &char| if (*a, *b) == ('O', '0') { 0.25 } else { 1.0 };
+            let result =
+                weighted_levenshtein_distance(&chars("O1O"), &chars("010"), 1.0, 1.0, cost);
+            assert_eq!(result, 0.5);
+        }
+
+        #[test]
+        fn cheaper_alternative_selected() {
+            // Deleting and inserting is cheaper than an expensive substitution
+            let result =
+                weighted_levenshtein_distance(&chars("a"), &chars("b"), 1.0, 1.0, |_, _| 5.0);
+            assert_eq!(result, 2.0);
+        }
+    }
+
+    mod osa_distance {
+        use super::{chars, osa_distance};
+
+        #[test]
+        fn transposition_costs_one_edit() {
+            assert_eq!(osa_distance(&chars("teh"), &chars("the")), 1);
+        }
+
+        #[test]
+        fn substring_not_edited_twice() {
+            assert_eq!(osa_distance(&chars("ca"), &chars("abc")), 3);
+        }
+
+        #[test]
+        fn matches_levenshtein_without_transpositions() {
+            assert_eq!(osa_distance(&chars("kitten"), &chars("sitting")), 3);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(osa_distance(&chars(""), &chars("abc")), 3);
+            assert_eq!(osa_distance(&chars("abc"), &chars("")), 3);
+            assert_eq!(osa_distance(&chars(""), &chars("")), 0);
+        }
+    }
+
+    mod damerau_levenshtein_distance {
+        use super::{chars, damerau_levenshtein_distance};
+
+        #[test]
+        fn transposition_costs_one_edit() {
+            assert_eq!(
+                damerau_levenshtein_distance(&chars("teh"), &chars("the")),
+                1
+            );
+        }
+
+        #[test]
+        fn substring_edited_twice() {
+            assert_eq!(damerau_levenshtein_distance(&chars("ca"), &chars("abc")), 2);
+        }
+
+        #[test]
+        fn matches_levenshtein_without_transpositions() {
+            let result = damerau_levenshtein_distance(&chars("kitten"), &chars("sitting"));
+            assert_eq!(result, 3);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(damerau_levenshtein_distance(&chars(""), &chars("abc")), 3);
+            assert_eq!(damerau_levenshtein_distance(&chars("abc"), &chars("")), 3);
+            assert_eq!(damerau_levenshtein_distance(&chars(""), &chars("")), 0);
+        }
+    }
+
+    mod jaro_similarity {
+        use super::{chars, jaro_similarity};
+        use crate::metric::testing::assert_close_to_reference;
+
+        #[test]
+        fn reference_values_correct() {
+            assert_close_to_reference(jaro_similarity(&chars("martha"), &chars("marhta")), 0.944);
+            assert_close_to_reference(jaro_similarity(&chars("dixon"), &chars("dicksonx")), 0.767);
+            assert_close_to_reference(
+                jaro_similarity(&chars("jellyfish"), &chars("smellyfish")),
+                0.896,
+            );
+        }
+
+        #[test]
+        fn identical_strings_score_one() {
+            assert_close_to_reference(jaro_similarity(&chars("abc"), &chars("abc")), 1.0);
+        }
+
+        #[test]
+        fn no_matches_score_zero() {
+            assert_close_to_reference(jaro_similarity(&chars("abc"), &chars("xyz")), 0.0);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_close_to_reference(jaro_similarity(&chars(""), &chars("")), 1.0);
+            assert_close_to_reference(jaro_similarity(&chars(""), &chars("abc")), 0.0);
+        }
+    }
+
+    mod lcs_length {
+        use super::{chars, lcs_length};
+
+        #[test]
+        fn length_correct() {
+            assert_eq!(lcs_length(&chars("ABCBDAB"), &chars("BDCABA")), 4);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(lcs_length(&chars(""), &chars("abc")), 0);
+            assert_eq!(lcs_length(&chars("abc"), &chars("")), 0);
+        }
+
+        #[test]
+        fn identical_strings_fully_shared() {
+            assert_eq!(lcs_length(&chars("abc"), &chars("abc")), 3);
+        }
+    }
+
+    mod hamming_distance {
+        use super::{chars, hamming_distance};
+
+        #[test]
+        fn distance_correct_within_bound() {
+            assert_eq!(hamming_distance(&chars("karolin"), &chars("kathrin"), 3), 3);
+        }
+
+        #[test]
+        fn exits_once_bound_exceeded() {
+            assert_eq!(hamming_distance(&chars("aaaa"), &chars("bbbb"), 1), 2);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(hamming_distance(&chars(""), &chars(""), 0), 0);
+        }
+    }
+
+    mod hamming_distance_packed {
+        use super::{hamming_distance, hamming_distance_packed};
+
+        #[test]
+        fn matches_unpacked_distance() {
+            let a = b"ACGTACGTACGTACGTACGTA";
+            let b = b"ACGAACGTTCGTACGTACGTC";
+            let expected = hamming_distance(a, b, usize::MAX);
+            assert_eq!(expected, 3);
+            assert_eq!(hamming_distance_packed(a, b, usize::MAX), expected);
+        }
+
+        #[test]
+        fn counts_every_differing_bit_position() {
+            // Bytes differing in a single high or low bit each count once
+            let a = [0x00, 0x80, 0x01, 0x10, 0xff, 0x00, 0x00, 0x00, 0x00];
+            let b = [0x80, 0x00, 0x00, 0x11, 0x00, 0x00, 0x00, 0x00, 0x01];
+            assert_eq!(hamming_distance_packed(&a, &b, usize::MAX), 6);
+        }
+
+        #[test]
+        fn exits_once_bound_exceeded() {
+            let result = hamming_distance_packed(&[0; 16], &[1; 16], 2);
+            assert!(result > 2);
+        }
+
+        #[test]
+        fn handles_short_and_empty() {
+            assert_eq!(hamming_distance_packed(b"abc", b"abd", 5), 1);
+            assert_eq!(hamming_distance_packed(b"", b"", 0), 0);
+        }
+    }
+
+    mod longest_common_substring_length {
+        use super::{chars, longest_common_substring_length};
+
+        #[test]
+        fn length_correct() {
+            let result = longest_common_substring_length(&chars("xabcdy"), &chars("zabcdw"));
+            assert_eq!(result, 4);
+        }
+
+        #[test]
+        fn subsequence_not_counted() {
+            let result = longest_common_substring_length(&chars("axbxc"), &chars("abc"));
+            assert_eq!(result, 1);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(
+                longest_common_substring_length(&chars(""), &chars("abc")),
+                0
+            );
+            assert_eq!(
+                longest_common_substring_length(&chars("abc"), &chars("")),
+                0
+            );
+        }
+    }
+
+    fn unit_score(a: &char, b: &char) -> f64 {
+        if a == b {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    mod global_alignment_score {
+        use super::{chars, global_alignment_score, unit_score};
+
+        #[test]
+        fn identical_sequences_score_all_matches() {
+            let a = chars("GATTACA");
+            assert_eq!(global_alignment_score(&a, &a, 2.0, 0.5, unit_score), 7.0);
+        }
+
+        #[test]
+        fn mismatches_scored() {
+            let result =
+                global_alignment_score(&chars("GATTACA"), &chars("GACTACA"), 2.0, 0.5, unit_score);
+            assert_eq!(result, 5.0);
+        }
+
+        #[test]
+        fn long_gap_penalised_affinely() {
+            // Six matches and a single gap of four symbols
+            let result = global_alignment_score(
+                &chars("ACGTTT"),
+                &chars("ACGXXXXTTT"),
+                2.0,
+                0.5,
+                unit_score,
+            );
+            assert_eq!(result, 6.0 - (2.0 + 3.0 * 0.5));
+        }
+
+        #[test]
+        fn one_long_gap_preferred_to_two_short_gaps() {
+            // ab--cd against abxycd, not a-b-cd style alignments
+            let result =
+                global_alignment_score(&chars("abcd"), &chars("abxycd"), 3.0, 1.0, unit_score);
+            assert_eq!(result, 4.0 - 4.0);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(
+                global_alignment_score(&chars(""), &chars(""), 2.0, 0.5, unit_score),
+                0.0
+            );
+            let result = global_alignment_score(&chars(""), &chars("abc"), 2.0, 0.5, unit_score);
+            assert_eq!(result, -3.0);
+            let result = global_alignment_score(&chars("abc"), &chars(""), 2.0, 0.5, unit_score);
+            assert_eq!(result, -3.0);
+        }
+    }
+
+    mod local_alignment_score {
+        use super::{chars, local_alignment_score, unit_score};
+
+        #[test]
+        fn best_substring_pair_scored() {
+            let result = local_alignment_score(
+                &chars("xxxxGATTACAyyyy"),
+                &chars("zzGATTACAzz"),
+                2.0,
+                0.5,
+                unit_score,
+            );
+            assert_eq!(result, 7.0);
+        }
+
+        #[test]
+        fn gapped_local_alignment_scored() {
+            let result = local_alignment_score(
+                &chars("qqACGTTTqq"),
+                &chars("ACGXXTTT"),
+                1.0,
+                0.5,
+                unit_score,
+            );
+            assert_eq!(result, 6.0 - 1.5);
+        }
+
+        #[test]
+        fn never_negative() {
+            let result = local_alignment_score(&chars("abc"), &chars("xyz"), 2.0, 0.5, unit_score);
+            assert_eq!(result, 0.0);
+            assert_eq!(
+                local_alignment_score(&chars(""), &chars("abc"), 2.0, 0.5, unit_score),
+                0.0
+            );
+        }
+    }
+}
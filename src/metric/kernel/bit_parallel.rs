@@ -0,0 +1,377 @@
+use std::cell::RefCell;
+
+const WORD_SIZE: usize = 64;
+const HIGH_BIT: u64 = 1 << (WORD_SIZE - 1);
+// Symbols below this value have their masks looked up in a table
+const TABLE_SIZE: usize = 256;
+
+/// Levenshtein distance, bounded by `max`. Returns `max + 1` as soon as the
+/// distance is known to exceed `max`.
+///
+/// Columns of the dynamic programming matrix are encoded as bit vectors of
+/// vertical deltas and advanced 64 cells at a time (Myers 1999). The shorter
+/// string is used as the pattern. Patterns longer than a machine word are
+/// split into blocks that pass horizontal deltas to one another (Hyyrö 2003).
+///
+/// Symbols are integers, so that the masks of each text symbol are found in
+/// constant time.
+pub fn levenshtein_distance_bit_parallel(a: &[u32], b: &[u32], max: usize) -> usize {
+    let exceeded = max + 1;
+
+    if a.len().abs_diff(b.len()) > max {
+        return exceeded;
+    }
+
+    let (pattern, text) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if pattern.is_empty() {
+        return text.len();
+    }
+
+    let dist = PatternMasks::with(pattern, |peq| {
+        if pattern.len() <= WORD_SIZE {
+            single_word(peq, pattern.len(), text, max)
+        } else {
+            blocks(peq, pattern.len(), text, max)
+        }
+    });
+    dist.min(exceeded)
+}
+
+fn single_word(peq: &PatternMasks, m: usize, text: &[u32], max: usize) -> usize {
+    let last = 1 << (m - 1);
+    // Vertical deltas are all +1 in the first column
+    let mut pv = !0u64;
+    let mut mv = 0u64;
+    let mut score = m;
+
+    for (j, &c) in text.iter().enumerate() {
+        let eq = peq.masks(c)[0];
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let ph = mv | !(xh | pv);
+        let mh = pv & xh;
+
+        if ph & last != 0 {
+            score += 1;
+        } else if mh & last != 0 {
+            score -= 1;
+        }
+
+        // Horizontal deltas are all +1 in the first row
+        let ph = (ph << 1) | 1;
+        let mh = mh << 1;
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+
+        // The score falls by at most one per remaining column
+        if score > max + (text.len() - j - 1) {
+            return max + 1;
+        }
+    }
+    score
+}
+
+fn blocks(peq: &PatternMasks, m: usize, text: &[u32], max: usize) -> usize {
+    let n_blocks = m.div_ceil(WORD_SIZE);
+    let last = 1 << ((m - 1) % WORD_SIZE);
+    let mut pv = vec![!0u64; n_blocks];
+    let mut mv = vec![0u64; n_blocks];
+    let mut score = m;
+
+    for (j, &c) in text.iter().enumerate() {
+        let masks = peq.masks(c);
+        // Horizontal delta entering the top of the block
+        let mut h_in: i8 = 1;
+
+        for k in 0..n_blocks {
+            let eq = masks[k];
+            let xv = eq | mv[k];
+            let eq = if h_in < 0 { eq | 1 } else { eq };
+            let xh = ((eq & pv[k]).wrapping_add(pv[k]) ^ pv[k]) | eq;
+            let mut ph = mv[k] | !(xh | pv[k]);
+            let mut mh = pv[k] & xh;
+
+            // Rows below the end of the pattern are padding and do not affect the score
+            if k + 1 == n_blocks {
+                if ph & last != 0 {
+                    score += 1;
+                } else if mh & last != 0 {
+                    score -= 1;
+                }
+            }
+
+            let h_out = if ph & HIGH_BIT != 0 {
+                1
+            } else if mh & HIGH_BIT != 0 {
+                -1
+            } else {
+                0
+            };
+
+            ph <<= 1;
+            mh <<= 1;
+            if h_in < 0 {
+                mh |= 1;
+            } else if h_in > 0 {
+                ph |= 1;
+            }
+            pv[k] = mh | !(xv | ph);
+            mv[k] = ph & xv;
+            h_in = h_out;
+        }
+
+        if score > max + (text.len() - j - 1) {
+            return max + 1;
+        }
+    }
+    score
+}
+
+// Bit masks of the positions at which each symbol occurs in the pattern, one
+// word per block. Masks of small symbols are stored in a table indexed by the
+// symbol, and those of other symbols in a list sorted by symbol. Each thread
+// keeps its masks between patterns, and only the table rows set by the last
+// pattern are cleared, so preparing a pattern takes time in its length.
+#[derive(Default)]
+struct PatternMasks {
+    n_blocks: usize,
+    table: Vec<u64>,
+    // Table rows set by the current pattern, possibly repeated
+    rows: Vec<usize>,
+    symbols: Vec<u32>,
+    other: Vec<u64>,
+    absent: Vec<u64>,
+}
+
+thread_local! {
+    static PATTERN_MASKS: RefCell<PatternMasks> = RefCell::new(PatternMasks::default());
+}
+
+impl PatternMasks {
+    fn new(pattern: &[u32]) -> PatternMasks {
+        let mut peq = PatternMasks::default();
+        peq.fill(pattern);
+        peq
+    }
+
+    // Pass the masks of `pattern` to `f`. Fresh masks are used if the
+    // thread's are already in use further up the stack.
+    fn with<R>(pattern: &[u32], f: impl FnOnce(&PatternMasks) -> R) -> R {
+        PATTERN_MASKS.with(|peq| match peq.try_borrow_mut() {
+            Ok(mut peq) => {
+                peq.fill(pattern);
+                f(&peq)
+            }
+            Err(_) => f(&PatternMasks::new(pattern)),
+        })
+    }
+
+    fn fill(&mut self, pattern: &[u32]) {
+        for &row in &self.rows {
+            self.table[row * self.n_blocks..(row + 1) * self.n_blocks].fill(0);
+        }
+        self.rows.clear();
+
+        let n_blocks = pattern.len().div_ceil(WORD_SIZE);
+        self.n_blocks = n_blocks;
+        if self.table.len() < TABLE_SIZE * n_blocks {
+            self.table.resize(TABLE_SIZE * n_blocks, 0);
+        }
+        self.absent.resize(n_blocks, 0);
+
+        self.symbols.clear();
+        self.symbols
+            .extend(pattern.iter().filter(|&&s| row(s).is_none()));
+        self.symbols.sort_unstable();
+        self.symbols.dedup();
+        self.other.clear();
+        self.other.resize(self.symbols.len() * n_blocks, 0);
+
+        for (i, &s) in pattern.iter().enumerate() {
+            let bit = 1 << (i % WORD_SIZE);
+            let block = i / WORD_SIZE;
+            match row(s) {
+                Some(row) => {
+                    self.table[row * n_blocks + block] |= bit;
+                    self.rows.push(row);
+                }
+                None => {
+                    let index = self.symbols.binary_search(&s).expect("symbol listed");
+                    self.other[index * n_blocks + block] |= bit;
+                }
+            }
+        }
+    }
+
+    fn masks(&self, s: u32) -> &[u64] {
+        let n_blocks = self.n_blocks;
+        match row(s) {
+            Some(row) => &self.table[row * n_blocks..(row + 1) * n_blocks],
+            None => match self.symbols.binary_search(&s) {
+                Ok(index) => &self.other[index * n_blocks..(index + 1) * n_blocks],
+                Err(_) => &self.absent,
+            },
+        }
+    }
+}
+
+// Row of the mask table holding the masks of symbol `s`, if any
+fn row(s: u32) -> Option<usize> {
+    usize::try_from(s).ok().filter(|&s| s < TABLE_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{levenshtein_distance_bit_parallel, PatternMasks};
+    use edit_distance::edit_distance;
+
+    fn chars(s: &str) -> Vec<u32> {
+        s.chars().map(u32::from).collect()
+    }
+
+    // Deterministic strings over a small alphabet, so that matches are frequent
+    fn generate(n: usize, len: usize, seed: u64) -> Vec<String> {
+        let mut state = seed;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as usize
+        };
+        (0..n)
+            .map(|_| {
+                (0..next() % len)
+                    .map(|_| ['a', 'b', 'c', 'd'][next() % 4])
+                    .collect()
+            })
+            .collect()
+    }
+
+    mod levenshtein_distance_bit_parallel {
+        use super::edit_distance;
+        use super::{chars, generate, levenshtein_distance_bit_parallel};
+
+        #[test]
+        fn distance_correct() {
+            let result = levenshtein_distance_bit_parallel(&chars("kitten"), &chars("sitting"), 10);
+            assert_eq!(result, 3);
+        }
+
+        #[test]
+        fn exceeded_bound_reported() {
+            let result = levenshtein_distance_bit_parallel(&chars("kitten"), &chars("sitting"), 2);
+            assert_eq!(result, 3);
+            let result = levenshtein_distance_bit_parallel(&chars("a"), &chars("abcd"), 2);
+            assert_eq!(result, 3);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_eq!(
+                levenshtein_distance_bit_parallel(&chars(""), &chars("abc"), 3),
+                3
+            );
+            assert_eq!(
+                levenshtein_distance_bit_parallel(&chars("abc"), &chars(""), 3),
+                3
+            );
+            assert_eq!(
+                levenshtein_distance_bit_parallel(&chars(""), &chars(""), 0),
+                0
+            );
+        }
+
+        #[test]
+        fn full_word_pattern_correct() {
+            let a = "a".repeat(64);
+            let b = format!("{}b", "a".repeat(63));
+            let result = levenshtein_distance_bit_parallel(&chars(&a), &chars(&b), 64);
+            assert_eq!(result, 1);
+        }
+
+        #[test]
+        fn matches_reference_on_short_strings() {
+            let words = generate(40, 64, 1);
+            for a in &words {
+                for b in &words {
+                    let expected = edit_distance(a, b);
+                    let result = levenshtein_distance_bit_parallel(&chars(a), &chars(b), 200);
+                    assert_eq!(result, expected, "{} {}", a, b);
+                }
+            }
+        }
+
+        #[test]
+        fn matches_reference_on_multi_block_strings() {
+            let words = generate(20, 300, 2);
+            for a in &words {
+                for b in &words {
+                    let expected = edit_distance(a, b);
+                    for max in [0, 5, 50, 300] {
+                        let result = levenshtein_distance_bit_parallel(&chars(a), &chars(b), max);
+                        assert_eq!(result, expected.min(max + 1), "{} {} {}", a, b, max);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn matches_reference_on_large_symbols() {
+        // Mix symbols found in the table with symbols found in the map
+        let words: Vec<String> = generate(20, 200, 3)
+            .iter()
+            .map(|w| w.replace('a', "日").replace('b', "😀"))
+            .collect();
+        for a in &words {
+            for b in &words {
+                let expected = edit_distance(a, b);
+                let result = levenshtein_distance_bit_parallel(&chars(a), &chars(b), 400);
+                assert_eq!(result, expected, "{} {}", a, b);
+            }
+        }
+    }
+
+    mod pattern_masks {
+        use super::{chars, PatternMasks};
+
+        #[test]
+        fn positions_encoded() {
+            let pattern = chars("abca");
+            let peq = PatternMasks::new(&pattern);
+            assert_eq!(peq.masks('a' as u32), &[0b1001]);
+            assert_eq!(peq.masks('c' as u32), &[0b0100]);
+            assert_eq!(peq.masks('z' as u32), &[0]);
+        }
+
+        #[test]
+        fn large_symbols_mapped() {
+            let pattern = chars("日本日a");
+            let peq = PatternMasks::new(&pattern);
+            assert_eq!(peq.masks('日' as u32), &[0b0101]);
+            assert_eq!(peq.masks('a' as u32), &[0b1000]);
+            assert_eq!(peq.masks('中' as u32), &[0]);
+        }
+
+        #[test]
+        fn previous_pattern_cleared() {
+            let mut peq = PatternMasks::new(&chars(&format!("{}日", "a".repeat(64))));
+            peq.fill(&chars("b日"));
+            assert_eq!(peq.masks('a' as u32), &[0]);
+            assert_eq!(peq.masks('b' as u32), &[0b01]);
+            assert_eq!(peq.masks('日' as u32), &[0b10]);
+            peq.fill(&chars(&"c".repeat(65)));
+            assert_eq!(peq.masks('b' as u32), &[0, 0]);
+            assert_eq!(peq.masks('日' as u32), &[0, 0]);
+            assert_eq!(peq.masks('c' as u32), &[!0, 1]);
+        }
+
+        #[test]
+        fn positions_split_across_blocks() {
+            let pattern = chars(&format!("{}b", "a".repeat(64)));
+            let peq = PatternMasks::new(&pattern);
+            assert_eq!(peq.masks('a' as u32), &[!0, 0]);
+            assert_eq!(peq.masks('b' as u32), &[0, 1]);
+        }
+    }
+}
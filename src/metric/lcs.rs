@@ -0,0 +1,214 @@
+use super::kernel::{lcs_length, longest_common_substring_length};
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+/// Longest common subsequence ratio thresholded on a minimum similarity.
+///
+/// The similarity of two strings is twice the length of their longest common
+/// subsequence over the sum of their lengths, measured in chars. Strings are
+/// similar if their similarity is at least `threshold`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LcsRatio {
+    threshold: f32,
+}
+
+impl LcsRatio {
+    /// Create a new metric.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<LcsRatio, ValueError> {
+        check_fraction(threshold)?;
+        Ok(LcsRatio { threshold })
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        ratio(&a, &b, lcs_length)
+    }
+}
+
+impl Metric for LcsRatio {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for LcsRatio {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        ratio(a, b, lcs_length) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        1.0 - ratio(a, b, lcs_length)
+    }
+}
+
+/// Longest common substring ratio thresholded on a minimum similarity.
+///
+/// The similarity of two strings is twice the length of their longest common
+/// contiguous substring over the sum of their lengths, measured in chars.
+/// Strings are similar if their similarity is at least `threshold`, which
+/// suits paths and identifiers sharing a long stem.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LongestCommonSubstring {
+    threshold: f32,
+}
+
+impl LongestCommonSubstring {
+    /// Create a new metric.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<LongestCommonSubstring, ValueError> {
+        check_fraction(threshold)?;
+        Ok(LongestCommonSubstring { threshold })
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        ratio(&a, &b, longest_common_substring_length)
+    }
+}
+
+impl Metric for LongestCommonSubstring {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for LongestCommonSubstring {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        ratio(a, b, longest_common_substring_length) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        1.0 - ratio(a, b, longest_common_substring_length)
+    }
+}
+
+fn ratio<T: Eq>(a: &[T], b: &[T], common_length: fn(&[T], &[T]) -> usize) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    (2 * common_length(a, b)) as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LcsRatio, LongestCommonSubstring};
+    use crate::metric::testing::assert_close;
+    use crate::metric::Metric;
+    use crate::ValueError;
+
+    mod lcs_ratio {
+        use super::{assert_close, LcsRatio, Metric, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(LcsRatio::new(1.01), Err(ValueError::InvalidFraction));
+            assert_eq!(LcsRatio::new(-0.01), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn similarity_correct() {
+            let metric = LcsRatio::new(0.0).unwrap();
+            assert_close(metric.similarity("ABCBDAB", "BDCABA"), 8.0 / 13.0);
+        }
+
+        #[test]
+        fn handles_empty() {
+            let metric = LcsRatio::new(1.0).unwrap();
+            assert!(metric.is_similar("", ""));
+            assert!(!LcsRatio::new(0.1).unwrap().is_similar("", "abc"));
+        }
+
+        #[test]
+        fn accepts_gapped_common_subsequence() {
+            let metric = LcsRatio::new(0.8).unwrap();
+            assert!(metric.is_similar("src/main.rs", "src/mian.rs"));
+        }
+
+        #[test]
+        fn measures_multi_byte_strings_in_chars() {
+            let metric = LcsRatio::new(0.0).unwrap();
+            assert_close(metric.similarity("日本語", "日本"), 0.8);
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = LcsRatio::new(0.75).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4, 5], &[1, 2, 9, 4, 5]);
+            assert!(metric.is_similar(a, b));
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = LcsRatio::new(0.0).unwrap();
+            assert_close(metric.distance("ABCBDAB", "BDCABA"), 5.0 / 13.0);
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4, 5], &[1, 2, 9, 4, 5]);
+            assert_close(metric.distance(a, b), 0.2);
+        }
+    }
+
+    mod longest_common_substring {
+        use super::{assert_close, LcsRatio, LongestCommonSubstring, Metric, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            let result = LongestCommonSubstring::new(2.0);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn similarity_correct() {
+            let metric = LongestCommonSubstring::new(0.0).unwrap();
+            assert_close(metric.similarity("xabcdy", "zabcdw"), 8.0 / 12.0);
+        }
+
+        #[test]
+        fn accepts_shared_stem() {
+            let metric = LongestCommonSubstring::new(0.7).unwrap();
+            assert!(metric.is_similar("logs/app-2023-01.txt", "logs/app-2023-02.log"));
+        }
+
+        #[test]
+        fn rejects_gapped_match() {
+            let metric = LongestCommonSubstring::new(0.5).unwrap();
+            assert!(!metric.is_similar("axbxcx", "aybycy"));
+            assert!(LcsRatio::new(0.5).unwrap().is_similar("axbxcx", "aybycy"));
+        }
+
+        #[test]
+        fn handles_empty() {
+            let metric = LongestCommonSubstring::new(1.0).unwrap();
+            assert!(metric.is_similar("", ""));
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = LongestCommonSubstring::new(0.0).unwrap();
+            assert_close(metric.distance("xabcdy", "zabcdw"), 4.0 / 12.0);
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = LongestCommonSubstring::new(0.75).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4, 5], &[1, 2, 9, 4, 5]);
+            assert!(!metric.is_similar(a, b));
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4, 5], &[1, 2, 3, 4, 9]);
+            assert!(metric.is_similar(a, b));
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use super::config::{edit_config_builders, EditConfig};
+use super::kernel::{levenshtein_distance_banded, levenshtein_distance_bit_parallel};
+use super::threshold::{within_max_edit, EditLimit};
+use super::Metric;
+use crate::ValueError;
+
+/// Algorithm used to compute Levenshtein distances.
+///
+/// Both kernels stop as soon as the distance is known to exceed the maximum
+/// edit distance. Sequences other than strings are always compared by the
+/// banded kernel, which only compares elements for equality.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LevenshteinKernel {
+    /// Bit-parallel algorithm processing 64 characters of the shorter string
+    /// per machine word (Myers/Hyyrö). Fastest in almost all cases.
+    #[default]
+    BitParallel,
+    /// Dynamic programming restricted to a diagonal band whose width grows
+    /// with the maximum edit distance (Ukkonen). Competitive for very long
+    /// strings with small edit fractions.
+    Banded,
+}
+
+/// Levenshtein distance thresholded on a fraction of the shorter string's length.
+///
+/// Strings are similar if the Levenshtein distance between them is at most
+/// `max_edit_frac` of the shorter string's length (rounded down). Lengths and
+/// edits are measured in [`Unit::Char`](super::Unit::Char) unless configured otherwise.
+///
+/// Sequences other than strings are compared element by element, ignoring
+/// the unit and the case and whitespace handling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Levenshtein {
+    config: EditConfig,
+    kernel: LevenshteinKernel,
+}
+
+impl Levenshtein {
+    /// Create a new metric.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(max_edit_frac: f32) -> Result<Levenshtein, ValueError> {
+        Ok(Levenshtein {
+            config: EditConfig::new(max_edit_frac)?,
+            kernel: LevenshteinKernel::BitParallel,
+        })
+    }
+
+    /// Set the algorithm used to compute distances.
+    pub fn with_kernel(mut self, kernel: LevenshteinKernel) -> Levenshtein {
+        self.kernel = kernel;
+        self
+    }
+}
+
+edit_config_builders!(Levenshtein, "number of edits", unit);
+
+impl Metric for Levenshtein {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| {
+                is_similar(a, b, &self.config.limit, self.kernel)
+            })
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.config
+            .unit
+            .with_symbols(a, b, &self.config.folding, |a, b| {
+                distance(a, b, self.kernel) as f64
+            })
+    }
+}
+
+impl<T: Eq + Sync> Metric<[T]> for Levenshtein {
+    fn is_similar(&self, a: &[T], b: &[T]) -> bool {
+        within_max_edit(a.len(), b.len(), &self.config.limit, |max_edit| {
+            levenshtein_distance_banded(a, b, max_edit)
+        })
+    }
+
+    fn distance(&self, a: &[T], b: &[T]) -> f64 {
+        levenshtein_distance_banded(a, b, a.len().max(b.len())) as f64
+    }
+}
+
+// The threshold is known up front, so the distance computation can stop as
+// soon as it is exceeded
+fn is_similar(a: &[u32], b: &[u32], limit: &EditLimit, kernel: LevenshteinKernel) -> bool {
+    within_max_edit(a.len(), b.len(), limit, |max_edit| match kernel {
+        LevenshteinKernel::BitParallel => levenshtein_distance_bit_parallel(a, b, max_edit),
+        LevenshteinKernel::Banded => levenshtein_distance_banded(a, b, max_edit),
+    })
+}
+
+// The distance never exceeds the length of the longer sequence, so bounding
+// the kernels by it yields the exact distance
+fn distance(a: &[u32], b: &[u32], kernel: LevenshteinKernel) -> usize {
+    let max = a.len().max(b.len());
+    match kernel {
+        LevenshteinKernel::BitParallel => levenshtein_distance_bit_parallel(a, b, max),
+        LevenshteinKernel::Banded => levenshtein_distance_banded(a, b, max),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_similar, EditLimit, Levenshtein, LevenshteinKernel};
+    use crate::metric::EditThreshold;
+
+    fn chars(s: &str) -> Vec<u32> {
+        s.chars().map(u32::from).collect()
+    }
+
+    fn shorter(frac: f32) -> EditLimit {
+        EditLimit::new(EditThreshold::FractionOfShorter(frac)).unwrap()
+    }
+
+    mod levenshtein {
+        use super::{EditThreshold, Levenshtein, LevenshteinKernel};
+        use crate::metric::{Case, Metric, Unit, Whitespace};
+        use crate::ValueError;
+
+        #[test]
+        fn new_rejects_fraction_below_zero() {
+            assert_eq!(Levenshtein::new(-0.1), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn new_rejects_fraction_above_one() {
+            assert_eq!(Levenshtein::new(1.1), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn metric_applies_max_edit_frac() {
+            let metric = Levenshtein::new(0.25).unwrap();
+            assert!(metric.is_similar("aaaa", "aaab"));
+            assert!(!metric.is_similar("aaaa", "aabb"));
+        }
+
+        #[test]
+        fn compares_sequences() {
+            let metric = Levenshtein::new(0.25).unwrap();
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[1, 2, 3, 5]);
+            assert!(metric.is_similar(a, b));
+            let (a, b): (&[u8], &[u8]) = (b"aaaa", b"aabb");
+            assert!(!metric.is_similar(a, b));
+        }
+
+        #[test]
+        fn case_insensitivity_applied() {
+            let metric = Levenshtein::new(0.0).unwrap();
+            assert!(!metric.is_similar("ACME Straße", "acme STRASSE"));
+            let metric = metric.with_case(Case::InsensitiveAscii);
+            assert!(metric.is_similar("ACME", "acme"));
+            assert!(!metric.is_similar("ÉCOLE", "école"));
+            let metric = metric.with_case(Case::Insensitive);
+            assert!(metric.is_similar("ÉCOLE", "école"));
+            assert!(metric.is_similar("ACME Straße", "acme STRASSE"));
+        }
+
+        #[test]
+        fn whitespace_handling_applied() {
+            let metric = Levenshtein::new(0.0)
+                .unwrap()
+                .with_whitespace(Whitespace::Collapsed);
+            assert!(metric.is_similar(" New  York\t", "New York"));
+            assert!(!metric.is_similar("New York", "NewYork"));
+            let metric = metric.with_whitespace(Whitespace::Ignored);
+            assert!(metric.is_similar("New York", "NewYork"));
+        }
+
+        #[test]
+        fn folding_applied_to_graphemes_and_distances() {
+            let metric = Levenshtein::new(0.0)
+                .unwrap()
+                .with_unit(Unit::Grapheme)
+                .with_case(Case::Insensitive)
+                .with_whitespace(Whitespace::Ignored);
+            assert!(metric.is_similar("E\u{301} 🇿🇦", "e\u{301}🇿🇦"));
+            assert_eq!(metric.distance("AB C", "abd"), 1.0);
+        }
+
+        #[test]
+        fn distance_counts_edits() {
+            let metric = Levenshtein::new(0.0).unwrap();
+            assert_eq!(metric.distance("kitten", "sitting"), 3.0);
+            assert_eq!(metric.distance("", "abc"), 3.0);
+            let metric = metric.with_kernel(LevenshteinKernel::Banded);
+            assert_eq!(metric.distance("kitten", "sitting"), 3.0);
+            let (a, b): (&[u32], &[u32]) = (&[1, 2, 3, 4], &[2, 3, 4, 5]);
+            assert_eq!(metric.distance(a, b), 2.0);
+        }
+
+        #[test]
+        fn threshold_policy_applied() {
+            let metric = Levenshtein::new(0.0).unwrap();
+            let metric = metric
+                .with_threshold(EditThreshold::FractionOfLonger(0.5))
+                .unwrap();
+            assert!(metric.is_similar("ab", "abcd"));
+            let metric = metric.with_threshold(EditThreshold::Absolute(1)).unwrap();
+            assert!(!metric.is_similar("ab", "abcd"));
+        }
+
+        #[test]
+        fn invalid_threshold_rejected() {
+            let metric = Levenshtein::new(0.5).unwrap();
+            let result = metric.with_threshold(EditThreshold::FractionOfMean(1.5));
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn edit_bounds_applied() {
+            let metric = Levenshtein::new(0.0).unwrap();
+            assert!(!metric.is_similar("ab", "ac"));
+            let metric = metric.with_edit_bounds(1, 2).unwrap();
+            assert!(metric.is_similar("ab", "ac"));
+        }
+
+        #[test]
+        fn inverted_edit_bounds_rejected() {
+            let result = Levenshtein::new(0.5).unwrap().with_edit_bounds(2, 1);
+            assert_eq!(result, Err(ValueError::InvalidEditBounds));
+        }
+
+        #[test]
+        fn kernels_agree() {
+            let inputs = ["kitten", "sitting", "saturday", "sunday", "日本", "中国"];
+            let bit_parallel = Levenshtein::new(0.5).unwrap();
+            let banded = bit_parallel.with_kernel(LevenshteinKernel::Banded);
+            for a in inputs {
+                for b in inputs {
+                    assert_eq!(bit_parallel.is_similar(a, b), banded.is_similar(a, b));
+                }
+            }
+        }
+
+        #[test]
+        fn grapheme_unit_applied() {
+            let metric = Levenshtein::new(0.34).unwrap();
+            assert!(!metric.is_similar("ab🇿🇦", "ab🇺🇸"));
+            let metric = metric.with_unit(Unit::Grapheme);
+            assert!(metric.is_similar("ab🇿🇦", "ab🇺🇸"));
+        }
+    }
+
+    mod is_similar {
+        use super::{chars, is_similar, shorter, LevenshteinKernel};
+
+        #[test]
+        fn is_similar_accepts_below_max_edit() {
+            let a = "aaaa";
+            let b = "aaab";
+            let result = is_similar(
+                &chars(a),
+                &chars(b),
+                &shorter(0.5),
+                LevenshteinKernel::default(),
+            );
+            assert!(result);
+        }
+
+        #[test]
+        fn is_similar_accepts_max_edit() {
+            let a = "aaaa";
+            let b = "aabb";
+            let result = is_similar(
+                &chars(a),
+                &chars(b),
+                &shorter(0.5),
+                LevenshteinKernel::default(),
+            );
+            assert!(result);
+        }
+
+        #[test]
+        fn is_similar_rejects_above_max_edit() {
+            let a = "a";
+            let b = "abbb";
+            let result = is_similar(
+                &chars(a),
+                &chars(b),
+                &shorter(1.0),
+                LevenshteinKernel::default(),
+            );
+            assert!(!result);
+        }
+
+        #[test]
+        fn is_similar_measures_multi_byte_strings_in_chars() {
+            // Two edits exceed half of two chars, but not half of six bytes
+            let result = is_similar(
+                &chars("日本"),
+                &chars("中国"),
+                &shorter(0.5),
+                LevenshteinKernel::default(),
+            );
+            assert!(!result);
+        }
+
+        #[test]
+        fn is_similar_prunes_on_char_length_difference() {
+            // Equal byte lengths, but three chars longer
+            let result = is_similar(
+                &chars("ééé"),
+                &chars("eeeeee"),
+                &shorter(0.5),
+                LevenshteinKernel::default(),
+            );
+            assert!(!result);
+        }
+
+        #[test]
+        fn is_similar_accepts_multi_byte_below_max_edit() {
+            let result = is_similar(
+                &chars("crème brûlée"),
+                &chars("creme brûlée"),
+                &shorter(0.1),
+                LevenshteinKernel::default(),
+            );
+            assert!(result);
+        }
+    }
+}
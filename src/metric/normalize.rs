@@ -0,0 +1,343 @@
+use super::Metric;
+use crate::ValueError;
+use regex::Regex;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Clone, Debug)]
+enum Step {
+    CaseFold,
+    Nfc,
+    Nfkc,
+    StripDiacritics,
+    CollapseWhitespace,
+    RemovePunctuation(Regex),
+    Replace(Regex, String),
+}
+
+impl Step {
+    fn apply<'a>(&self, s: Cow<'a, str>) -> Cow<'a, str> {
+        match self {
+            Step::CaseFold => Cow::Owned(caseless::default_case_fold_str(&s)),
+            Step::Nfc => Cow::Owned(s.nfc().collect()),
+            Step::Nfkc => Cow::Owned(s.nfkc().collect()),
+            Step::StripDiacritics => {
+                Cow::Owned(s.nfd().filter(|&c| !is_combining_mark(c)).nfc().collect())
+            }
+            Step::CollapseWhitespace => {
+                Cow::Owned(s.split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            Step::RemovePunctuation(regex) => replace_all(s, regex, ""),
+            Step::Replace(regex, replacement) => replace_all(s, regex, replacement),
+        }
+    }
+}
+
+fn replace_all<'a>(s: Cow<'a, str>, regex: &Regex, replacement: &str) -> Cow<'a, str> {
+    match regex.replace_all(&s, replacement) {
+        Cow::Borrowed(_) => s,
+        Cow::Owned(replaced) => Cow::Owned(replaced),
+    }
+}
+
+/// Pipeline of text transformations applied to strings before comparison.
+///
+/// Steps are applied in the order in which they were added.
+///
+/// # Examples
+/// ```
+/// # fn main() -> Result<(), clustr::ValueError> {
+/// use clustr::metric::Normalizer;
+///
+/// let normalizer = Normalizer::new()
+///     .with_case_folding()
+///     .with_diacritics_stripped()
+///     .with_punctuation_removed()
+///     .with_whitespace_collapsed()
+///     .with_replacement(r"\b(ltd|limited)\b", "")?
+///     .with_whitespace_collapsed();
+///
+/// assert_eq!(normalizer.normalize("  Café  Nöel, LTD. "), "cafe noel");
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Normalizer {
+    steps: Vec<Step>,
+}
+
+impl Normalizer {
+    /// Create an empty pipeline, which leaves strings unchanged.
+    pub fn new() -> Normalizer {
+        Normalizer { steps: Vec::new() }
+    }
+
+    /// Apply Unicode default case folding, e.g. "Straße" becomes "strasse".
+    pub fn with_case_folding(mut self) -> Normalizer {
+        self.steps.push(Step::CaseFold);
+        self
+    }
+
+    /// Apply Unicode canonical composition (NFC).
+    pub fn with_nfc(mut self) -> Normalizer {
+        self.steps.push(Step::Nfc);
+        self
+    }
+
+    /// Apply Unicode compatibility composition (NFKC), e.g. "ﬁ" becomes "fi".
+    pub fn with_nfkc(mut self) -> Normalizer {
+        self.steps.push(Step::Nfkc);
+        self
+    }
+
+    /// Remove combining marks, e.g. "café" becomes "cafe". The result is in NFC.
+    pub fn with_diacritics_stripped(mut self) -> Normalizer {
+        self.steps.push(Step::StripDiacritics);
+        self
+    }
+
+    /// Trim leading and trailing whitespace and replace each run of
+    /// whitespace with a single space.
+    pub fn with_whitespace_collapsed(mut self) -> Normalizer {
+        self.steps.push(Step::CollapseWhitespace);
+        self
+    }
+
+    /// Remove characters in the Unicode punctuation categories.
+    pub fn with_punctuation_removed(mut self) -> Normalizer {
+        let regex = Regex::new(r"\p{P}+").expect("punctuation pattern is valid");
+        self.steps.push(Step::RemovePunctuation(regex));
+        self
+    }
+
+    /// Replace every match of the regular expression `pattern` with
+    /// `replacement`, which may refer to capture groups as `$1` or `$name`.
+    ///
+    /// Returns [`ValueError::InvalidPattern`] if `pattern` is not a valid
+    /// regular expression.
+    pub fn with_replacement(
+        mut self,
+        pattern: &str,
+        replacement: &str,
+    ) -> Result<Normalizer, ValueError> {
+        let regex = Regex::new(pattern).map_err(|_| ValueError::InvalidPattern)?;
+        self.steps
+            .push(Step::Replace(regex, replacement.to_string()));
+        Ok(self)
+    }
+
+    /// Apply the pipeline to `s`.
+    pub fn normalize<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        self.steps
+            .iter()
+            .fold(Cow::Borrowed(s), |s, step| step.apply(s))
+    }
+}
+
+/// Metric comparing normalized strings.
+///
+/// Both strings are passed through a [`Normalizer`] before being compared by
+/// the wrapped metric. Clusters still contain the original strings.
+///
+/// The normalized form of every input is cached when the metric is fitted,
+/// before clustering starts, so each input is normalized once rather than on
+/// every comparison. Strings not seen during fitting are normalized as they
+/// are compared.
+#[derive(Clone, Debug)]
+pub struct Normalized<M> {
+    normalizer: Normalizer,
+    metric: M,
+    normalized: HashMap<String, String>,
+}
+
+impl<M: Metric> Normalized<M> {
+    /// Create a new metric applying `normalizer` before comparing strings with `metric`.
+    pub fn new(normalizer: Normalizer, metric: M) -> Normalized<M> {
+        Normalized {
+            normalizer,
+            metric,
+            normalized: HashMap::new(),
+        }
+    }
+
+    fn normalize<'a>(&'a self, s: &'a str) -> Cow<'a, str> {
+        match self.normalized.get(s) {
+            Some(normalized) => Cow::Borrowed(normalized),
+            None => self.normalizer.normalize(s),
+        }
+    }
+
+    fn normalize_all<'a>(&'a self, inputs: &[&'a str]) -> Vec<Cow<'a, str>> {
+        inputs.iter().map(|s| self.normalize(s)).collect()
+    }
+}
+
+impl<M: Metric> Metric for Normalized<M> {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.metric
+            .is_similar(&self.normalize(a), &self.normalize(b))
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.metric.distance(&self.normalize(a), &self.normalize(b))
+    }
+
+    fn validate(&self, inputs: &[&str]) -> Result<(), ValueError> {
+        let normalized = self.normalize_all(inputs);
+        let normalized: Vec<&str> = normalized.iter().map(|s| s.as_ref()).collect();
+        self.metric.validate(&normalized)
+    }
+
+    fn fit(&mut self, inputs: &[&str]) {
+        let mut cache = HashMap::new();
+        for &s in inputs {
+            if !cache.contains_key(s) {
+                cache.insert(s.to_string(), self.normalizer.normalize(s).into_owned());
+            }
+        }
+
+        let normalized: Vec<&str> = inputs.iter().map(|&s| cache[s].as_str()).collect();
+        self.metric.fit(&normalized);
+        self.normalized = cache;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Normalized, Normalizer};
+    use crate::metric::{Hamming, Levenshtein, Metric, TfIdfCosine};
+    use crate::ValueError;
+
+    mod normalizer {
+        use super::{Normalizer, ValueError};
+        use std::borrow::Cow;
+
+        #[test]
+        fn empty_pipeline_borrows() {
+            let normalized = Normalizer::new().normalize("Abc");
+            assert!(matches!(normalized, Cow::Borrowed("Abc")));
+        }
+
+        #[test]
+        fn case_folding_applied() {
+            let normalizer = Normalizer::new().with_case_folding();
+            assert_eq!(normalizer.normalize("Straße ΣΑΣ"), "strasse σασ");
+        }
+
+        #[test]
+        fn composition_applied() {
+            let decomposed = "cafe\u{301}";
+            assert_eq!(Normalizer::new().with_nfc().normalize(decomposed), "café");
+            assert_eq!(Normalizer::new().with_nfc().normalize("ﬁ"), "ﬁ");
+            assert_eq!(Normalizer::new().with_nfkc().normalize("ﬁ"), "fi");
+        }
+
+        #[test]
+        fn diacritics_stripped() {
+            let normalizer = Normalizer::new().with_diacritics_stripped();
+            assert_eq!(normalizer.normalize("Crème Brûlée"), "Creme Brulee");
+            assert_eq!(normalizer.normalize("cafe\u{301}"), "cafe");
+        }
+
+        #[test]
+        fn whitespace_collapsed() {
+            let normalizer = Normalizer::new().with_whitespace_collapsed();
+            assert_eq!(normalizer.normalize(" a \t b\n\nc "), "a b c");
+        }
+
+        #[test]
+        fn punctuation_removed() {
+            let normalizer = Normalizer::new().with_punctuation_removed();
+            assert_eq!(
+                normalizer.normalize("O'Brien-Smith, «Ltd.»"),
+                "OBrienSmith Ltd"
+            );
+        }
+
+        #[test]
+        fn replacement_applied() {
+            let normalizer = Normalizer::new()
+                .with_replacement(r"(\d+)-(\d+)", "$2$1")
+                .unwrap();
+            assert_eq!(normalizer.normalize("12-34"), "3412");
+        }
+
+        #[test]
+        fn replacement_rejects_invalid_pattern() {
+            let result = Normalizer::new().with_replacement("(", "");
+            assert!(matches!(result, Err(ValueError::InvalidPattern)));
+        }
+
+        #[test]
+        fn steps_applied_in_order() {
+            let normalizer = Normalizer::new()
+                .with_replacement("A", "b")
+                .unwrap()
+                .with_case_folding();
+            assert_eq!(normalizer.normalize("Aa"), "ba");
+            let normalizer = Normalizer::new()
+                .with_case_folding()
+                .with_replacement("A", "b")
+                .unwrap();
+            assert_eq!(normalizer.normalize("Aa"), "aa");
+        }
+    }
+
+    mod normalized {
+        use super::{
+            Hamming, Levenshtein, Metric, Normalized, Normalizer, TfIdfCosine, ValueError,
+        };
+
+        #[test]
+        fn compares_normalized_strings() {
+            let normalizer = Normalizer::new()
+                .with_case_folding()
+                .with_whitespace_collapsed();
+            let metric = Normalized::new(normalizer, Levenshtein::new(0.0).unwrap());
+            assert!(metric.is_similar("Acme  Corp", "ACME CORP "));
+            assert!(!metric.is_similar("Acme Corp", "Acme Co"));
+        }
+
+        #[test]
+        fn distance_of_normalized_strings() {
+            let normalizer = Normalizer::new().with_case_folding();
+            let metric = Normalized::new(normalizer, Levenshtein::new(0.0).unwrap());
+            assert_eq!(metric.distance("ACME", "acne"), 1.0);
+        }
+
+        #[test]
+        fn validates_normalized_strings() {
+            let normalizer = Normalizer::new().with_whitespace_collapsed();
+            let metric = Normalized::new(normalizer, Hamming::new(0.5).unwrap());
+            assert_eq!(metric.validate(&["ab", " ab "]), Ok(()));
+            assert_eq!(
+                metric.validate(&["ab", "a b"]),
+                Err(ValueError::UnequalLengths)
+            );
+        }
+
+        #[test]
+        fn fits_normalized_strings() {
+            let normalizer = Normalizer::new().with_case_folding();
+            let mut metric = Normalized::new(normalizer, TfIdfCosine::new(0.5).unwrap());
+            metric.fit(&["The red", "the blue", "THE GREEN"]);
+            assert!(!metric.is_similar("the red", "The Blue"));
+        }
+
+        #[test]
+        fn fitted_inputs_normalized_once() {
+            let normalizer = Normalizer::new().with_case_folding();
+            let mut metric = Normalized::new(normalizer, Levenshtein::new(0.0).unwrap());
+            metric.fit(&["Acme", "ACME", "Acme"]);
+            assert_eq!(metric.normalized.len(), 2);
+            assert_eq!(metric.normalized["ACME"], "acme");
+            assert!(metric.is_similar("Acme", "ACME"));
+            // Strings not seen during fitting are normalized as they are compared
+            assert!(metric.is_similar("ACME", "acme"));
+            assert!(!metric.is_similar("ACME", "Acne"));
+        }
+    }
+}
@@ -0,0 +1,222 @@
+mod double_metaphone;
+mod nysiis;
+mod soundex;
+
+use super::{Levenshtein, Metric};
+use crate::ValueError;
+use double_metaphone::double_metaphone;
+use nysiis::nysiis;
+use soundex::soundex;
+
+/// Algorithm used to encode strings by their pronunciation.
+///
+/// Encoders are designed for single English names. Characters other than
+/// letters are ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhoneticEncoder {
+    /// American Soundex: the first letter followed by three digits, e.g.
+    /// "R163" for "Robert" and "Rupert".
+    Soundex,
+    /// Double Metaphone: a primary and an alternate code of at most four
+    /// characters, accounting for spellings of non-English origin.
+    DoubleMetaphone,
+    /// New York State Identification and Intelligence System code.
+    Nysiis,
+}
+
+impl PhoneticEncoder {
+    /// Encode `s`, returning one code, or two distinct codes for
+    /// [`PhoneticEncoder::DoubleMetaphone`] if the alternate differs from the
+    /// primary. Returns no codes if `s` contains nothing to encode.
+    pub fn encode(&self, s: &str) -> Vec<String> {
+        let codes = match self {
+            PhoneticEncoder::Soundex => vec![soundex(s)],
+            PhoneticEncoder::Nysiis => vec![nysiis(s)],
+            PhoneticEncoder::DoubleMetaphone => {
+                let (primary, alternate) = double_metaphone(s);
+                if primary == alternate {
+                    vec![primary]
+                } else {
+                    vec![primary, alternate]
+                }
+            }
+        };
+        codes.into_iter().filter(|code| !code.is_empty()).collect()
+    }
+}
+
+/// Phonetic similarity.
+///
+/// By default strings are similar if they share a phonetic code, e.g. "Smith"
+/// and "Smyth" under [`PhoneticEncoder::Soundex`]. Codes may instead be compared
+/// with the Levenshtein rule to tolerate small differences in pronunciation.
+/// Strings without a code, such as numbers, are similar only if identical.
+///
+/// The distance is the smallest Levenshtein distance between the strings'
+/// codes if codes are compared with the Levenshtein rule, and otherwise 0 for
+/// similar and 1 for dissimilar strings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Phonetic {
+    encoder: PhoneticEncoder,
+    codes: Option<Levenshtein>,
+}
+
+impl Phonetic {
+    /// Create a new metric requiring equal codes.
+    pub fn new(encoder: PhoneticEncoder) -> Phonetic {
+        Phonetic {
+            encoder,
+            codes: None,
+        }
+    }
+
+    /// Compare codes by Levenshtein distance, allowing at most `max_edit_frac`
+    /// of the shorter code's length in edits.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn with_max_edit_frac(mut self, max_edit_frac: f32) -> Result<Phonetic, ValueError> {
+        self.codes = Some(Levenshtein::new(max_edit_frac)?);
+        Ok(self)
+    }
+}
+
+impl Metric for Phonetic {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        let codes_a = self.encoder.encode(a);
+        let codes_b = self.encoder.encode(b);
+
+        if codes_a.is_empty() || codes_b.is_empty() {
+            return a == b;
+        }
+
+        codes_a.iter().any(|x| {
+            codes_b.iter().any(|y| match &self.codes {
+                None => x == y,
+                Some(levenshtein) => levenshtein.is_similar(x.as_str(), y.as_str()),
+            })
+        })
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        let codes_a = self.encoder.encode(a);
+        let codes_b = self.encoder.encode(b);
+
+        if codes_a.is_empty() || codes_b.is_empty() {
+            return if a == b { 0.0 } else { 1.0 };
+        }
+
+        let pairs = codes_a
+            .iter()
+            .flat_map(|x| codes_b.iter().map(move |y| (x.as_str(), y.as_str())));
+        match &self.codes {
+            None => {
+                if pairs.clone().any(|(x, y)| x == y) {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Some(levenshtein) => pairs
+                .map(|(x, y)| levenshtein.distance(x, y))
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Phonetic, PhoneticEncoder};
+    use crate::metric::Metric;
+    use crate::ValueError;
+
+    mod encode {
+        use super::PhoneticEncoder;
+
+        #[test]
+        fn single_code_returned() {
+            assert_eq!(PhoneticEncoder::Soundex.encode("Robert"), vec!["R163"]);
+            assert_eq!(PhoneticEncoder::Nysiis.encode("Knuth"), vec!["NAT"]);
+        }
+
+        #[test]
+        fn distinct_alternate_returned() {
+            let codes = PhoneticEncoder::DoubleMetaphone.encode("Smith");
+            assert_eq!(codes, vec!["SM0", "XMT"]);
+            let codes = PhoneticEncoder::DoubleMetaphone.encode("Thomas");
+            assert_eq!(codes, vec!["TMS"]);
+        }
+
+        #[test]
+        fn empty_codes_dropped() {
+            assert!(PhoneticEncoder::Soundex.encode("1234").is_empty());
+            assert!(PhoneticEncoder::DoubleMetaphone.encode("").is_empty());
+        }
+    }
+
+    mod phonetic {
+        use super::{Metric, Phonetic, PhoneticEncoder, ValueError};
+
+        #[test]
+        fn with_max_edit_frac_rejects_invalid_fraction() {
+            let result = Phonetic::new(PhoneticEncoder::Soundex).with_max_edit_frac(1.5);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn accepts_homophones() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex);
+            assert!(metric.is_similar("Smith", "Smyth"));
+            let metric = Phonetic::new(PhoneticEncoder::DoubleMetaphone);
+            assert!(metric.is_similar("Smith", "Smyth"));
+            let metric = Phonetic::new(PhoneticEncoder::Nysiis);
+            assert!(metric.is_similar("Macintosh", "McIntosh"));
+        }
+
+        #[test]
+        fn alternate_codes_matched() {
+            let metric = Phonetic::new(PhoneticEncoder::DoubleMetaphone);
+            assert!(metric.is_similar("Catherine", "Kathryn"));
+            assert!(metric.is_similar("Smith", "Schmidt"));
+        }
+
+        #[test]
+        fn rejects_different_codes() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex);
+            assert!(!metric.is_similar("Robert", "Roper"));
+        }
+
+        #[test]
+        fn codes_compared_by_edit_distance() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex)
+                .with_max_edit_frac(0.25)
+                .unwrap();
+            assert!(metric.is_similar("Robert", "Roper"));
+            assert!(!metric.is_similar("Robert", "Alice"));
+        }
+
+        #[test]
+        fn strings_without_codes_compared_exactly() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex);
+            assert!(metric.is_similar("123", "123"));
+            assert!(!metric.is_similar("123", "124"));
+            assert!(!metric.is_similar("123", "Robert"));
+        }
+
+        #[test]
+        fn distance_of_exact_codes() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex);
+            assert_eq!(metric.distance("Robert", "Rupert"), 0.0);
+            assert_eq!(metric.distance("Robert", "Alice"), 1.0);
+        }
+
+        #[test]
+        fn distance_of_codes_by_edit_distance() {
+            let metric = Phonetic::new(PhoneticEncoder::Soundex)
+                .with_max_edit_frac(0.25)
+                .unwrap();
+            // R163 and R150
+            assert_eq!(metric.distance("Robert", "Rubin"), 2.0);
+        }
+    }
+}
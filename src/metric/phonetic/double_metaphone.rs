@@ -0,0 +1,712 @@
+// Port of the Apache Commons Codec implementation of Lawrence Philips'
+// Double Metaphone algorithm.
+
+const MAX_CODE_LEN: usize = 4;
+
+const SILENT_START: [&str; 5] = ["GN", "KN", "PN", "WR", "PS"];
+const L_R_N_M_B_H_F_V_W_SPACE: [&str; 10] = ["L", "R", "N", "M", "B", "H", "F", "V", "W", " "];
+const ES_EP_EB_EL_EY_IB_IL_IN_IE_EI_ER: [&str; 11] = [
+    "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+];
+const L_T_K_S_N_M_B_Z: [&str; 8] = ["L", "T", "K", "S", "N", "M", "B", "Z"];
+
+/// Primary and alternate Double Metaphone codes of `s`, each at most four
+/// characters long.
+pub fn double_metaphone(s: &str) -> (String, String) {
+    let value: Vec<char> = s.trim().to_uppercase().chars().collect();
+    let mut result = MetaphoneResult::default();
+
+    if value.is_empty() {
+        return (result.primary, result.alternate);
+    }
+
+    let word = Word {
+        slavo_germanic: is_slavo_germanic(&value),
+        value,
+    };
+    let mut index = if word.is_silent_start() { 1 } else { 0 };
+
+    while !result.is_complete() && index < word.len() as isize {
+        index = match word.char_at(index) {
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                if index == 0 {
+                    result.append('A');
+                }
+                index + 1
+            }
+            'B' => {
+                result.append('P');
+                word.skip_double(index, 'B')
+            }
+            'Ç' => {
+                result.append('S');
+                index + 1
+            }
+            'C' => word.handle_c(&mut result, index),
+            'D' => word.handle_d(&mut result, index),
+            'F' => {
+                result.append('F');
+                word.skip_double(index, 'F')
+            }
+            'G' => word.handle_g(&mut result, index),
+            'H' => word.handle_h(&mut result, index),
+            'J' => word.handle_j(&mut result, index),
+            'K' => {
+                result.append('K');
+                word.skip_double(index, 'K')
+            }
+            'L' => word.handle_l(&mut result, index),
+            'M' => {
+                result.append('M');
+                if word.condition_m0(index) {
+                    index + 2
+                } else {
+                    index + 1
+                }
+            }
+            'N' => {
+                result.append('N');
+                word.skip_double(index, 'N')
+            }
+            'Ñ' => {
+                result.append('N');
+                index + 1
+            }
+            'P' => word.handle_p(&mut result, index),
+            'Q' => {
+                result.append('K');
+                word.skip_double(index, 'Q')
+            }
+            'R' => word.handle_r(&mut result, index),
+            'S' => word.handle_s(&mut result, index),
+            'T' => word.handle_t(&mut result, index),
+            'V' => {
+                result.append('F');
+                word.skip_double(index, 'V')
+            }
+            'W' => word.handle_w(&mut result, index),
+            'X' => word.handle_x(&mut result, index),
+            'Z' => word.handle_z(&mut result, index),
+            _ => index + 1,
+        };
+    }
+    (result.primary, result.alternate)
+}
+
+fn is_slavo_germanic(value: &[char]) -> bool {
+    let s: String = value.iter().collect();
+    s.contains('W') || s.contains('K') || s.contains("CZ") || s.contains("WITZ")
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+}
+
+#[derive(Default)]
+struct MetaphoneResult {
+    primary: String,
+    alternate: String,
+}
+
+impl MetaphoneResult {
+    fn is_complete(&self) -> bool {
+        self.primary.chars().count() >= MAX_CODE_LEN
+            && self.alternate.chars().count() >= MAX_CODE_LEN
+    }
+
+    fn append(&mut self, c: char) {
+        self.append_primary(c);
+        self.append_alternate(c);
+    }
+
+    fn append_pair(&mut self, primary: char, alternate: char) {
+        self.append_primary(primary);
+        self.append_alternate(alternate);
+    }
+
+    fn append_str(&mut self, s: &str) {
+        self.append_str_pair(s, s);
+    }
+
+    fn append_str_pair(&mut self, primary: &str, alternate: &str) {
+        primary.chars().for_each(|c| self.append_primary(c));
+        alternate.chars().for_each(|c| self.append_alternate(c));
+    }
+
+    fn append_primary(&mut self, c: char) {
+        if self.primary.chars().count() < MAX_CODE_LEN {
+            self.primary.push(c);
+        }
+    }
+
+    fn append_alternate(&mut self, c: char) {
+        if self.alternate.chars().count() < MAX_CODE_LEN {
+            self.alternate.push(c);
+        }
+    }
+}
+
+struct Word {
+    value: Vec<char>,
+    slavo_germanic: bool,
+}
+
+impl Word {
+    fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    fn last(&self) -> isize {
+        self.value.len() as isize - 1
+    }
+
+    // Returns '\0' outside of the word
+    fn char_at(&self, index: isize) -> char {
+        if index < 0 {
+            return '\0';
+        }
+        self.value.get(index as usize).copied().unwrap_or('\0')
+    }
+
+    // True if the substring of `length` chars at `start` equals any of `criteria`
+    fn contains(&self, start: isize, length: usize, criteria: &[&str]) -> bool {
+        if start < 0 || start as usize + length > self.value.len() {
+            return false;
+        }
+        let start = start as usize;
+        let target = &self.value[start..start + length];
+        criteria
+            .iter()
+            .any(|c| c.chars().count() == length && c.chars().zip(target).all(|(x, y)| x == *y))
+    }
+
+    fn is_silent_start(&self) -> bool {
+        self.contains(0, 2, &SILENT_START)
+    }
+
+    fn skip_double(&self, index: isize, c: char) -> isize {
+        if self.char_at(index + 1) == c {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_c(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.condition_c0(index) {
+            result.append('K');
+            index + 2
+        } else if index == 0 && self.contains(index, 6, &["CAESAR"]) {
+            result.append('S');
+            index + 2
+        } else if self.contains(index, 2, &["CH"]) {
+            self.handle_ch(result, index)
+        } else if self.contains(index, 2, &["CZ"]) && !self.contains(index - 2, 4, &["WICZ"]) {
+            // "Czerny"
+            result.append_pair('S', 'X');
+            index + 2
+        } else if self.contains(index + 1, 3, &["CIA"]) {
+            // "focaccia"
+            result.append('X');
+            index + 3
+        } else if self.contains(index, 2, &["CC"]) && !(index == 1 && self.char_at(0) == 'M') {
+            // Double "cc" but not "McClelland"
+            self.handle_cc(result, index)
+        } else if self.contains(index, 2, &["CK", "CG", "CQ"]) {
+            result.append('K');
+            index + 2
+        } else if self.contains(index, 2, &["CI", "CE", "CY"]) {
+            // Italian vs. English
+            if self.contains(index, 3, &["CIO", "CIE", "CIA"]) {
+                result.append_pair('S', 'X');
+            } else {
+                result.append('S');
+            }
+            index + 2
+        } else {
+            result.append('K');
+            if self.contains(index + 1, 2, &[" C", " Q", " G"]) {
+                // "Mac Caffrey", "Mac Gregor"
+                index + 3
+            } else if self.contains(index + 1, 1, &["C", "K", "Q"])
+                && !self.contains(index + 1, 2, &["CE", "CI"])
+            {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_cc(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index + 2, 1, &["I", "E", "H"]) && !self.contains(index + 2, 2, &["HU"]) {
+            // "bellocchio" but not "bacchus"
+            if (index == 1 && self.char_at(index - 1) == 'A')
+                || self.contains(index - 1, 5, &["UCCEE", "UCCES"])
+            {
+                // "accident", "accede", "succeed"
+                result.append_str("KS");
+            } else {
+                // "bacci", "bertucci", other Italian
+                result.append('X');
+            }
+            index + 3
+        } else {
+            // Pierce's rule
+            result.append('K');
+            index + 2
+        }
+    }
+
+    fn handle_ch(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if index > 0 && self.contains(index, 4, &["CHAE"]) {
+            // "Michael"
+            result.append_pair('K', 'X');
+        } else if self.condition_ch0(index) || self.condition_ch1(index) {
+            // Greek roots ("chemistry", "chorus"), or Germanic 'ch' for 'kh' sound
+            result.append('K');
+        } else if index > 0 {
+            if self.contains(0, 2, &["MC"]) {
+                result.append('K');
+            } else {
+                result.append_pair('X', 'K');
+            }
+        } else {
+            result.append('X');
+        }
+        index + 2
+    }
+
+    fn handle_d(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index, 2, &["DG"]) {
+            if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+                // "Edge"
+                result.append('J');
+                index + 3
+            } else {
+                // "Edgar"
+                result.append_str("TK");
+                index + 2
+            }
+        } else if self.contains(index, 2, &["DT", "DD"]) {
+            result.append('T');
+            index + 2
+        } else {
+            result.append('T');
+            index + 1
+        }
+    }
+
+    fn handle_g(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        let next = self.char_at(index + 1);
+
+        if next == 'H' {
+            self.handle_gh(result, index)
+        } else if next == 'N' {
+            if index == 1 && is_vowel(self.char_at(0)) && !self.slavo_germanic {
+                result.append_str_pair("KN", "N");
+            } else if !self.contains(index + 2, 2, &["EY"]) && next != 'Y' && !self.slavo_germanic {
+                result.append_str_pair("N", "KN");
+            } else {
+                result.append_str("KN");
+            }
+            index + 2
+        } else if self.contains(index + 1, 2, &["LI"]) && !self.slavo_germanic {
+            result.append_str_pair("KL", "L");
+            index + 2
+        } else if index == 0
+            && (next == 'Y' || self.contains(index + 1, 2, &ES_EP_EB_EL_EY_IB_IL_IN_IE_EI_ER))
+        {
+            // -ges-, -gep-, -gel-, -gie- at beginning
+            result.append_pair('K', 'J');
+            index + 2
+        } else if (self.contains(index + 1, 2, &["ER"]) || next == 'Y')
+            && !self.contains(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.contains(index - 1, 1, &["E", "I"])
+            && !self.contains(index - 1, 3, &["RGY", "OGY"])
+        {
+            // -ger-, -gy-
+            result.append_pair('K', 'J');
+            index + 2
+        } else if self.contains(index + 1, 1, &["E", "I", "Y"])
+            || self.contains(index - 1, 4, &["AGGI", "OGGI"])
+        {
+            // Italian "biaggi"
+            if self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+                || self.contains(index + 1, 2, &["ET"])
+            {
+                // Obvious Germanic
+                result.append('K');
+            } else if self.contains(index + 1, 3, &["IER"]) {
+                result.append('J');
+            } else {
+                result.append_pair('J', 'K');
+            }
+            index + 2
+        } else if next == 'G' {
+            result.append('K');
+            index + 2
+        } else {
+            result.append('K');
+            index + 1
+        }
+    }
+
+    fn handle_gh(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if index > 0 && !is_vowel(self.char_at(index - 1)) {
+            result.append('K');
+        } else if index == 0 {
+            if self.char_at(index + 2) == 'I' {
+                result.append('J');
+            } else {
+                result.append('K');
+            }
+        } else if (index > 1 && self.contains(index - 2, 1, &["B", "H", "D"]))
+            || (index > 2 && self.contains(index - 3, 1, &["B", "H", "D"]))
+            || (index > 3 && self.contains(index - 4, 1, &["B", "H"]))
+        {
+            // Parker's rule, "hugh"
+        } else if index > 2
+            && self.char_at(index - 1) == 'U'
+            && self.contains(index - 3, 1, &["C", "G", "L", "R", "T"])
+        {
+            // "laugh", "McLaughlin", "cough", "gough", "rough", "tough"
+            result.append('F');
+        } else if index > 0 && self.char_at(index - 1) != 'I' {
+            result.append('K');
+        }
+        index + 2
+    }
+
+    fn handle_h(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        // Only keep if first and before a vowel, or between two vowels
+        if (index == 0 || is_vowel(self.char_at(index - 1))) && is_vowel(self.char_at(index + 1)) {
+            result.append('H');
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_j(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index, 4, &["JOSE"]) || self.contains(0, 4, &["SAN "]) {
+            // Obvious Spanish, "Jose", "San Jacinto"
+            if (index == 0 && (self.char_at(index + 4) == ' ' || self.len() == 4))
+                || self.contains(0, 4, &["SAN "])
+            {
+                result.append('H');
+            } else {
+                result.append_pair('J', 'H');
+            }
+            return index + 1;
+        }
+
+        if index == 0 {
+            result.append_pair('J', 'A');
+        } else if is_vowel(self.char_at(index - 1))
+            && !self.slavo_germanic
+            && matches!(self.char_at(index + 1), 'A' | 'O')
+        {
+            result.append_pair('J', 'H');
+        } else if index == self.last() {
+            result.append_pair('J', ' ');
+        } else if !self.contains(index + 1, 1, &L_T_K_S_N_M_B_Z)
+            && !self.contains(index - 1, 1, &["S", "K", "L"])
+        {
+            result.append('J');
+        }
+        self.skip_double(index, 'J')
+    }
+
+    fn handle_l(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.char_at(index + 1) == 'L' {
+            if self.condition_l0(index) {
+                result.append_primary('L');
+            } else {
+                result.append('L');
+            }
+            index + 2
+        } else {
+            result.append('L');
+            index + 1
+        }
+    }
+
+    fn handle_p(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            result.append('F');
+            index + 2
+        } else {
+            result.append('P');
+            if self.contains(index + 1, 1, &["P", "B"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_r(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if index == self.last()
+            && !self.slavo_germanic
+            && self.contains(index - 2, 2, &["IE"])
+            && !self.contains(index - 4, 2, &["ME", "MA"])
+        {
+            // French, e.g. "Rogier"
+            result.append_alternate('R');
+        } else {
+            result.append('R');
+        }
+        self.skip_double(index, 'R')
+    }
+
+    fn handle_s(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index - 1, 3, &["ISL", "YSL"]) {
+            // "island", "isle", "carlisle", "carlysle"
+            index + 1
+        } else if index == 0 && self.contains(index, 5, &["SUGAR"]) {
+            result.append_pair('X', 'S');
+            index + 1
+        } else if self.contains(index, 2, &["SH"]) {
+            if self.contains(index + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                // Germanic
+                result.append('S');
+            } else {
+                result.append('X');
+            }
+            index + 2
+        } else if self.contains(index, 3, &["SIO", "SIA"]) || self.contains(index, 4, &["SIAN"]) {
+            // Italian and Armenian
+            if self.slavo_germanic {
+                result.append('S');
+            } else {
+                result.append_pair('S', 'X');
+            }
+            index + 3
+        } else if (index == 0 && self.contains(index + 1, 1, &["M", "N", "L", "W"]))
+            || self.contains(index + 1, 1, &["Z"])
+        {
+            // German and anglicisations, "smith" matches "schmidt", "snider"
+            // matches "schneider". Also -sz- in Slavic languages.
+            result.append_pair('S', 'X');
+            self.skip_double(index, 'Z')
+        } else if self.contains(index, 2, &["SC"]) {
+            self.handle_sc(result, index)
+        } else {
+            if index == self.last() && self.contains(index - 2, 2, &["AI", "OI"]) {
+                // French, e.g. "resnais", "artois"
+                result.append_alternate('S');
+            } else {
+                result.append('S');
+            }
+            if self.contains(index + 1, 1, &["S", "Z"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_sc(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.char_at(index + 2) == 'H' {
+            // Schlesinger's rule
+            if self.contains(index + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                // Dutch origin, e.g. "school", "schooner"
+                if self.contains(index + 3, 2, &["ER", "EN"]) {
+                    // "schermerhorn", "schenker"
+                    result.append_str_pair("X", "SK");
+                } else {
+                    result.append_str("SK");
+                }
+            } else if index == 0 && !is_vowel(self.char_at(3)) && self.char_at(3) != 'W' {
+                result.append_pair('X', 'S');
+            } else {
+                result.append('X');
+            }
+        } else if self.contains(index + 2, 1, &["I", "E", "Y"]) {
+            result.append('S');
+        } else {
+            result.append_str("SK");
+        }
+        index + 3
+    }
+
+    fn handle_t(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index, 4, &["TION"]) || self.contains(index, 3, &["TIA", "TCH"]) {
+            result.append('X');
+            index + 3
+        } else if self.contains(index, 2, &["TH"]) || self.contains(index, 3, &["TTH"]) {
+            if self.contains(index + 2, 2, &["OM", "AM"])
+                || self.contains(0, 4, &["VAN ", "VON "])
+                || self.contains(0, 3, &["SCH"])
+            {
+                // "thomas", "thames" or Germanic
+                result.append('T');
+            } else {
+                result.append_pair('0', 'T');
+            }
+            index + 2
+        } else {
+            result.append('T');
+            if self.contains(index + 1, 1, &["T", "D"]) {
+                index + 2
+            } else {
+                index + 1
+            }
+        }
+    }
+
+    fn handle_w(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.contains(index, 2, &["WR"]) {
+            result.append('R');
+            index + 2
+        } else if index == 0
+            && (is_vowel(self.char_at(index + 1)) || self.contains(index, 2, &["WH"]))
+        {
+            if is_vowel(self.char_at(index + 1)) {
+                // "Wasserman" should match "Vasserman"
+                result.append_pair('A', 'F');
+            } else {
+                // "Uomo" should match "Womo"
+                result.append('A');
+            }
+            index + 1
+        } else if (index == self.last() && is_vowel(self.char_at(index - 1)))
+            || self.contains(index - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.contains(0, 3, &["SCH"])
+        {
+            // "Arnow" should match "Arnoff"
+            result.append_alternate('F');
+            index + 1
+        } else if self.contains(index, 4, &["WICZ", "WITZ"]) {
+            // Polish, e.g. "filipowicz"
+            result.append_str_pair("TS", "FX");
+            index + 4
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_x(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if index == 0 {
+            result.append('S');
+            return index + 1;
+        }
+        let french = index == self.last()
+            && (self.contains(index - 3, 3, &["IAU", "EAU"])
+                || self.contains(index - 2, 2, &["AU", "OU"]));
+        if !french {
+            // Silent in French, e.g. "breaux"
+            result.append_str("KS");
+        }
+        if self.contains(index + 1, 1, &["C", "X"]) {
+            index + 2
+        } else {
+            index + 1
+        }
+    }
+
+    fn handle_z(&self, result: &mut MetaphoneResult, index: isize) -> isize {
+        if self.char_at(index + 1) == 'H' {
+            // Chinese pinyin, e.g. "zhao"
+            result.append('J');
+            return index + 2;
+        }
+        if self.contains(index + 1, 2, &["ZO", "ZI", "ZA"])
+            || (self.slavo_germanic && index > 0 && self.char_at(index - 1) != 'T')
+        {
+            result.append_str_pair("S", "TS");
+        } else {
+            result.append('S');
+        }
+        self.skip_double(index, 'Z')
+    }
+
+    fn condition_c0(&self, index: isize) -> bool {
+        if self.contains(index, 4, &["CHIA"]) {
+            return true;
+        }
+        if index <= 1 || is_vowel(self.char_at(index - 2)) || !self.contains(index - 1, 3, &["ACH"])
+        {
+            return false;
+        }
+        let c = self.char_at(index + 2);
+        (c != 'I' && c != 'E') || self.contains(index - 2, 6, &["BACHER", "MACHER"])
+    }
+
+    fn condition_ch0(&self, index: isize) -> bool {
+        index == 0
+            && (self.contains(index + 1, 5, &["HARAC", "HARIS"])
+                || self.contains(index + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+            && !self.contains(0, 5, &["CHORE"])
+    }
+
+    fn condition_ch1(&self, index: isize) -> bool {
+        self.contains(0, 4, &["VAN ", "VON "])
+            || self.contains(0, 3, &["SCH"])
+            || self.contains(index - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+            || self.contains(index + 2, 1, &["T", "S"])
+            || ((self.contains(index - 1, 1, &["A", "O", "U", "E"]) || index == 0)
+                && (self.contains(index + 2, 1, &L_R_N_M_B_H_F_V_W_SPACE)
+                    || index + 1 == self.last()))
+    }
+
+    fn condition_l0(&self, index: isize) -> bool {
+        let len = self.len() as isize;
+        if index == len - 3 && self.contains(index - 1, 4, &["ILLO", "ILLA", "ALLE"]) {
+            return true;
+        }
+        (self.contains(len - 2, 2, &["AS", "OS"]) || self.contains(len - 1, 1, &["A", "O"]))
+            && self.contains(index - 1, 4, &["ALLE"])
+    }
+
+    fn condition_m0(&self, index: isize) -> bool {
+        if self.char_at(index + 1) == 'M' {
+            return true;
+        }
+        self.contains(index - 1, 3, &["UMB"])
+            && (index + 1 == self.last() || self.contains(index + 2, 2, &["ER"]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::double_metaphone;
+
+    fn codes(primary: &str, alternate: &str) -> (String, String) {
+        (primary.to_string(), alternate.to_string())
+    }
+
+    #[test]
+    fn reference_codes_correct() {
+        assert_eq!(double_metaphone("Thomas"), codes("TMS", "TMS"));
+        assert_eq!(double_metaphone("Smith"), codes("SM0", "XMT"));
+        assert_eq!(double_metaphone("Schmidt"), codes("XMT", "SMT"));
+        assert_eq!(double_metaphone("Catherine"), codes("K0RN", "KTRN"));
+        assert_eq!(double_metaphone("Kathryn"), codes("K0RN", "KTRN"));
+        assert_eq!(double_metaphone("Michael"), codes("MKL", "MXL"));
+        assert_eq!(double_metaphone("Jose"), codes("HS", "HS"));
+        assert_eq!(double_metaphone("Knight"), codes("NT", "NT"));
+        assert_eq!(double_metaphone("Laugh"), codes("LF", "LF"));
+        assert_eq!(double_metaphone("Wasserman"), codes("ASRM", "FSRM"));
+        assert_eq!(double_metaphone("Filipowicz"), codes("FLPT", "FLPF"));
+        assert_eq!(double_metaphone("Xavier"), codes("SF", "SFR"));
+    }
+
+    #[test]
+    fn codes_truncated() {
+        assert_eq!(double_metaphone("Rumpelstiltskin").0.len(), 4);
+    }
+
+    #[test]
+    fn case_and_surrounding_whitespace_ignored() {
+        assert_eq!(double_metaphone("  smith "), double_metaphone("SMITH"));
+    }
+
+    #[test]
+    fn handles_empty() {
+        assert_eq!(double_metaphone(""), codes("", ""));
+        assert_eq!(double_metaphone("   "), codes("", ""));
+    }
+}
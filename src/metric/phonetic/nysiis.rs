@@ -0,0 +1,121 @@
+/// NYSIIS code of `s`.
+///
+/// Characters other than ASCII letters are ignored and the code is not
+/// truncated. Returns an empty string if `s` contains no ASCII letters.
+pub fn nysiis(s: &str) -> String {
+    let mut name: Vec<u8> = s
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    if name.is_empty() {
+        return String::new();
+    }
+
+    // Translate first characters
+    if name.starts_with(b"MAC") {
+        name[1] = b'C';
+    } else if name.starts_with(b"KN") {
+        name.remove(0);
+    } else if name.starts_with(b"K") {
+        name[0] = b'C';
+    } else if name.starts_with(b"PH") || name.starts_with(b"PF") {
+        name[..2].copy_from_slice(b"FF");
+    } else if name.starts_with(b"SCH") {
+        name[..3].copy_from_slice(b"SSS");
+    }
+
+    // Translate last characters
+    if name.ends_with(b"EE") || name.ends_with(b"IE") {
+        name.truncate(name.len() - 2);
+        name.push(b'Y');
+    } else if [b"DT", b"RT", b"RD", b"NT", b"ND"]
+        .iter()
+        .any(|suffix| name.ends_with(*suffix))
+    {
+        name.truncate(name.len() - 2);
+        name.push(b'D');
+    }
+
+    let at = |i: usize| name.get(i).copied();
+    let mut key = vec![name[0]];
+    let mut i = 1;
+    while i < name.len() {
+        let c = name[i];
+        let translated: &[u8] = match c {
+            b'E' if at(i + 1) == Some(b'V') => {
+                i += 1;
+                b"AF"
+            }
+            b'A' | b'E' | b'I' | b'O' | b'U' => b"A",
+            b'Q' => b"G",
+            b'Z' => b"S",
+            b'M' => b"N",
+            b'K' if at(i + 1) == Some(b'N') => b"N",
+            b'K' => b"C",
+            b'S' if name[i + 1..].starts_with(b"CH") => {
+                i += 2;
+                b"SS"
+            }
+            b'P' if at(i + 1) == Some(b'H') => {
+                i += 1;
+                b"F"
+            }
+            b'H' if !is_vowel(name[i - 1]) || !at(i + 1).is_some_and(is_vowel) => {
+                if is_vowel(name[i - 1]) {
+                    b"A"
+                } else {
+                    &name[i - 1..i]
+                }
+            }
+            b'W' if is_vowel(name[i - 1]) => &name[i - 1..i],
+            _ => &name[i..i + 1],
+        };
+        if translated.last() != key.last() {
+            key.extend_from_slice(translated);
+        }
+        i += 1;
+    }
+
+    if key.len() > 1 && key.ends_with(b"S") {
+        key.pop();
+    }
+    if key.ends_with(b"AY") {
+        key.remove(key.len() - 2);
+    }
+    if key.len() > 1 && key.ends_with(b"A") {
+        key.pop();
+    }
+
+    String::from_utf8(key).expect("code contains only ASCII letters")
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'A' | b'E' | b'I' | b'O' | b'U')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::nysiis;
+
+    #[test]
+    fn reference_codes_correct() {
+        assert_eq!(nysiis("Macintosh"), "MCANT");
+        assert_eq!(nysiis("Knuth"), "NAT");
+        assert_eq!(nysiis("Bishop"), "BASAP");
+        assert_eq!(nysiis("Smith"), "SNAT");
+        assert_eq!(nysiis("Schmidt"), "SNAD");
+    }
+
+    #[test]
+    fn ignores_case_and_non_letters() {
+        assert_eq!(nysiis("mac-intosh"), nysiis("MACINTOSH"));
+    }
+
+    #[test]
+    fn handles_empty() {
+        assert_eq!(nysiis(""), "");
+        assert_eq!(nysiis("42"), "");
+    }
+}
@@ -0,0 +1,84 @@
+/// American Soundex code of `s`: the first letter followed by three digits.
+///
+/// Characters other than ASCII letters are ignored. Returns an empty string if
+/// `s` contains no ASCII letters.
+pub fn soundex(s: &str) -> String {
+    let mut letters = s
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|c| c.to_ascii_uppercase());
+
+    let first = match letters.next() {
+        Some(c) => c,
+        None => return String::new(),
+    };
+    let mut code = String::with_capacity(4);
+    code.push(first);
+
+    let mut last = digit(first);
+    for c in letters {
+        if code.len() == 4 {
+            break;
+        }
+        match c {
+            // H and W do not separate letters with the same digit
+            'H' | 'W' => continue,
+            'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => last = None,
+            _ => {
+                let d = digit(c);
+                if d != last {
+                    code.extend(d);
+                    last = d;
+                }
+            }
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+fn digit(c: char) -> Option<char> {
+    match c {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::soundex;
+
+    #[test]
+    fn reference_codes_correct() {
+        assert_eq!(soundex("Robert"), "R163");
+        assert_eq!(soundex("Rupert"), "R163");
+        assert_eq!(soundex("Ashcraft"), "A261");
+        assert_eq!(soundex("Tymczak"), "T522");
+        assert_eq!(soundex("Pfister"), "P236");
+        assert_eq!(soundex("Honeyman"), "H555");
+    }
+
+    #[test]
+    fn pads_short_codes() {
+        assert_eq!(soundex("Lee"), "L000");
+    }
+
+    #[test]
+    fn ignores_case_and_non_letters() {
+        assert_eq!(soundex("o'brien"), soundex("OBRIEN"));
+    }
+
+    #[test]
+    fn handles_empty() {
+        assert_eq!(soundex(""), "");
+        assert_eq!(soundex("123"), "");
+    }
+}
@@ -0,0 +1,284 @@
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+use std::collections::HashSet;
+
+const DEFAULT_Q: usize = 2;
+
+/// Jaccard index of character q-gram sets thresholded on a minimum similarity.
+///
+/// Strings are similar if the number of q-grams they share, divided by the
+/// number of distinct q-grams in either string, is at least `threshold`.
+/// Q-gram sets ignore the order in which substrings occur, which makes the
+/// metric robust to reordered words and cheap to compute on long strings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Jaccard {
+    threshold: f32,
+    grams: QGrams,
+}
+
+impl Jaccard {
+    /// Create a new metric using unpadded bigrams.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<Jaccard, ValueError> {
+        check_fraction(threshold)?;
+        Ok(Jaccard {
+            threshold,
+            grams: QGrams::default(),
+        })
+    }
+
+    /// Set the number of characters in each gram.
+    ///
+    /// Returns [`ValueError::InvalidGramSize`] if `q` is zero.
+    pub fn with_q(mut self, q: usize) -> Result<Jaccard, ValueError> {
+        self.grams = self.grams.with_q(q)?;
+        Ok(self)
+    }
+
+    /// Pad strings with `q - 1` boundary markers on both sides, so that
+    /// leading and trailing characters contribute as many grams as inner ones.
+    pub fn with_padding(mut self, padding: bool) -> Jaccard {
+        self.grams.padding = padding;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = (self.grams.symbols(a), self.grams.symbols(b));
+        let (shared, n_a, n_b) = self.grams.overlap(&a, &b);
+        let union = n_a + n_b - shared;
+
+        if union == 0 {
+            return if a == b { 1.0 } else { 0.0 };
+        }
+        shared as f64 / union as f64
+    }
+}
+
+impl Metric for Jaccard {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+/// Sørensen-Dice coefficient of character q-gram sets thresholded on a minimum
+/// similarity.
+///
+/// Strings are similar if twice the number of q-grams they share, divided by
+/// the total number of distinct q-grams in each string, is at least
+/// `threshold`. Shared q-grams carry more weight than under [`Jaccard`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SorensenDice {
+    threshold: f32,
+    grams: QGrams,
+}
+
+impl SorensenDice {
+    /// Create a new metric using unpadded bigrams.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<SorensenDice, ValueError> {
+        check_fraction(threshold)?;
+        Ok(SorensenDice {
+            threshold,
+            grams: QGrams::default(),
+        })
+    }
+
+    /// Set the number of characters in each gram.
+    ///
+    /// Returns [`ValueError::InvalidGramSize`] if `q` is zero.
+    pub fn with_q(mut self, q: usize) -> Result<SorensenDice, ValueError> {
+        self.grams = self.grams.with_q(q)?;
+        Ok(self)
+    }
+
+    /// Pad strings with `q - 1` boundary markers on both sides, so that
+    /// leading and trailing characters contribute as many grams as inner ones.
+    pub fn with_padding(mut self, padding: bool) -> SorensenDice {
+        self.grams.padding = padding;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (a, b) = (self.grams.symbols(a), self.grams.symbols(b));
+        let (shared, n_a, n_b) = self.grams.overlap(&a, &b);
+
+        if n_a + n_b == 0 {
+            return if a == b { 1.0 } else { 0.0 };
+        }
+        (2 * shared) as f64 / (n_a + n_b) as f64
+    }
+}
+
+impl Metric for SorensenDice {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct QGrams {
+    q: usize,
+    padding: bool,
+}
+
+impl Default for QGrams {
+    fn default() -> QGrams {
+        QGrams {
+            q: DEFAULT_Q,
+            padding: false,
+        }
+    }
+}
+
+impl QGrams {
+    fn with_q(self, q: usize) -> Result<QGrams, ValueError> {
+        if q == 0 {
+            return Err(ValueError::InvalidGramSize);
+        }
+        Ok(QGrams { q, ..self })
+    }
+
+    // Padding markers are represented as `None` so they cannot collide with input characters
+    fn symbols(&self, s: &str) -> Vec<Option<char>> {
+        let pad = if self.padding { self.q - 1 } else { 0 };
+        let mut symbols = vec![None; pad];
+        symbols.extend(s.chars().map(Some));
+        symbols.extend(std::iter::repeat_n(None, pad));
+        symbols
+    }
+
+    // Returns the number of shared grams and the number of distinct grams in each string
+    fn overlap(&self, a: &[Option<char>], b: &[Option<char>]) -> (usize, usize, usize) {
+        let a: HashSet<&[Option<char>]> = a.windows(self.q).collect();
+        let b: HashSet<&[Option<char>]> = b.windows(self.q).collect();
+        (a.intersection(&b).count(), a.len(), b.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Jaccard, QGrams, SorensenDice};
+    use crate::metric::testing::assert_close;
+    use crate::metric::Metric;
+    use crate::ValueError;
+
+    mod jaccard {
+        use super::{assert_close, Jaccard, Metric, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(Jaccard::new(1.1), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn with_q_rejects_zero() {
+            let result = Jaccard::new(0.5).unwrap().with_q(0);
+            assert_eq!(result, Err(ValueError::InvalidGramSize));
+        }
+
+        #[test]
+        fn similarity_correct() {
+            // {ni, ig, gh, ht} and {na, ac, ch, ht} share one of seven bigrams
+            let metric = Jaccard::new(0.5).unwrap();
+            assert_close(metric.similarity("night", "nacht"), 1.0 / 7.0);
+        }
+
+        #[test]
+        fn word_order_ignored() {
+            let metric = Jaccard::new(0.6).unwrap();
+            assert!(metric.is_similar("black cotton shirt", "cotton shirt black"));
+        }
+
+        #[test]
+        fn padding_applied() {
+            // {#a, ab, b#} and {#a, ac, c#} share one of five bigrams
+            let metric = Jaccard::new(0.5).unwrap().with_padding(true);
+            assert_close(metric.similarity("ab", "ac"), 1.0 / 5.0);
+        }
+
+        #[test]
+        fn strings_shorter_than_q_compared_exactly() {
+            let metric = Jaccard::new(0.5).unwrap().with_q(3).unwrap();
+            assert_close(metric.similarity("ab", "ab"), 1.0);
+            assert_close(metric.similarity("ab", "ac"), 0.0);
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = Jaccard::new(0.0).unwrap();
+            assert_close(metric.distance("ab", "ab"), 0.0);
+            assert_close(metric.distance("ab", "cd"), 1.0);
+        }
+    }
+
+    mod sorensen_dice {
+        use super::{assert_close, Metric, SorensenDice, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert_eq!(SorensenDice::new(-0.1), Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn with_q_rejects_zero() {
+            let result = SorensenDice::new(0.5).unwrap().with_q(0);
+            assert_eq!(result, Err(ValueError::InvalidGramSize));
+        }
+
+        #[test]
+        fn similarity_correct() {
+            let metric = SorensenDice::new(0.5).unwrap();
+            assert_close(metric.similarity("night", "nacht"), 0.25);
+        }
+
+        #[test]
+        fn threshold_applied() {
+            let metric = SorensenDice::new(0.25).unwrap();
+            assert!(metric.is_similar("night", "nacht"));
+            assert!(!metric.is_similar("night", "day"));
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = SorensenDice::new(0.0).unwrap();
+            assert_close(metric.distance("night", "nacht"), 0.75);
+        }
+    }
+
+    mod q_grams {
+        use super::QGrams;
+
+        #[test]
+        fn padding_added_to_both_sides() {
+            let grams = QGrams::default().with_q(3).unwrap();
+            let grams = QGrams {
+                padding: true,
+                ..grams
+            };
+            let expected = vec![None, None, Some('a'), None, None];
+            assert_eq!(grams.symbols("a"), expected);
+        }
+
+        #[test]
+        fn distinct_grams_counted() {
+            let grams = QGrams::default();
+            let a = grams.symbols("aaa");
+            let b = grams.symbols("aab");
+            assert_eq!(grams.overlap(&a, &b), (1, 1, 2));
+        }
+    }
+}
@@ -0,0 +1,22 @@
+// Assertions shared by the metric tests
+
+// Assert that `result` equals `expected` up to rounding errors
+pub fn assert_close(result: f64, expected: f64) {
+    assert!(
+        (result - expected).abs() < 1e-6,
+        "{} != {}",
+        result,
+        expected
+    );
+}
+
+// Assert that `result` agrees with `expected`, a published reference value
+// given to three decimal places
+pub fn assert_close_to_reference(result: f64, expected: f64) {
+    assert!(
+        (result - expected).abs() < 1e-3,
+        "{} != {}",
+        result,
+        expected
+    );
+}
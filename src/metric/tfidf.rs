@@ -0,0 +1,290 @@
+use super::threshold::check_fraction;
+use super::{Metric, Tokenizer};
+use crate::ValueError;
+
+use std::collections::HashMap;
+
+/// Terms weighted by [`TfIdfCosine`].
+#[derive(Clone, Copy, Debug)]
+enum Terms {
+    Tokens(Tokenizer),
+    CharGrams(usize),
+}
+
+impl Terms {
+    fn extract(&self, s: &str) -> Vec<String> {
+        match *self {
+            Terms::Tokens(tokenizer) => tokenizer
+                .tokenize(s)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            Terms::CharGrams(q) => {
+                let chars: Vec<char> = s.chars().collect();
+                if chars.is_empty() {
+                    return Vec::new();
+                }
+                if chars.len() <= q {
+                    return vec![s.to_string()];
+                }
+                chars.windows(q).map(|w| w.iter().collect()).collect()
+            }
+        }
+    }
+}
+
+// Sparse vector of (term id, weight) pairs sorted by term id
+type SparseVector = Vec<(usize, f64)>;
+
+/// Cosine similarity of TF-IDF weighted term vectors thresholded on a minimum
+/// similarity.
+///
+/// Terms are weighted by their frequency in a string and by their smoothed
+/// inverse document frequency `ln((1 + n) / (1 + df)) + 1` across the inputs
+/// to be clustered, so rare shared terms contribute more to the similarity
+/// than common ones. Inverse document frequencies are fitted, and the vectors
+/// of all inputs cached, before clustering starts. An unfitted metric weights
+/// all terms equally.
+#[derive(Clone, Debug)]
+pub struct TfIdfCosine {
+    threshold: f32,
+    terms: Terms,
+    vocabulary: HashMap<String, usize>,
+    idf: Vec<f64>,
+    n_documents: usize,
+    vectors: HashMap<String, SparseVector>,
+}
+
+impl TfIdfCosine {
+    /// Create a new metric using whitespace separated tokens as terms.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<TfIdfCosine, ValueError> {
+        check_fraction(threshold)?;
+        Ok(TfIdfCosine {
+            threshold,
+            terms: Terms::Tokens(Tokenizer::Whitespace),
+            vocabulary: HashMap::new(),
+            idf: Vec::new(),
+            n_documents: 0,
+            vectors: HashMap::new(),
+        })
+    }
+
+    /// Use tokens produced by `tokenizer` as terms. Discards fitted weights.
+    pub fn with_tokenizer(self, tokenizer: Tokenizer) -> TfIdfCosine {
+        self.with_terms(Terms::Tokens(tokenizer))
+    }
+
+    /// Use character q-grams as terms. Strings of at most `q` characters form
+    /// a single term. Discards fitted weights.
+    ///
+    /// Returns [`ValueError::InvalidGramSize`] if `q` is zero.
+    pub fn with_char_grams(self, q: usize) -> Result<TfIdfCosine, ValueError> {
+        if q == 0 {
+            return Err(ValueError::InvalidGramSize);
+        }
+        Ok(self.with_terms(Terms::CharGrams(q)))
+    }
+
+    fn with_terms(self, terms: Terms) -> TfIdfCosine {
+        TfIdfCosine {
+            terms,
+            ..TfIdfCosine::new(self.threshold).expect("threshold already validated")
+        }
+    }
+
+    fn idf(&self, document_frequency: usize) -> f64 {
+        ((1 + self.n_documents) as f64 / (1 + document_frequency) as f64).ln() + 1.0
+    }
+
+    // Terms outside of the vocabulary are assigned ids from `unseen`, shared
+    // between the vectors being compared.
+    fn vectorize(&self, s: &str, unseen: &mut HashMap<String, usize>) -> SparseVector {
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for term in self.terms.extract(s) {
+            let id = match self.vocabulary.get(&term) {
+                Some(&id) => id,
+                None => {
+                    let next_id = self.vocabulary.len() + unseen.len();
+                    *unseen.entry(term).or_insert(next_id)
+                }
+            };
+            *counts.entry(id).or_insert(0) += 1;
+        }
+
+        let mut vector: SparseVector = counts
+            .into_iter()
+            .map(|(id, count)| {
+                let idf = self.idf.get(id).copied().unwrap_or_else(|| self.idf(0));
+                (id, count as f64 * idf)
+            })
+            .collect();
+        vector.sort_unstable_by_key(|&(id, _)| id);
+
+        let norm = vector.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+        vector.iter_mut().for_each(|(_, w)| *w /= norm);
+        vector
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let (x, y) = match (self.vectors.get(a), self.vectors.get(b)) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                let mut unseen = HashMap::new();
+                let x = self.vectorize(a, &mut unseen);
+                let y = self.vectorize(b, &mut unseen);
+                return cosine(&x, &y, a == b);
+            }
+        };
+        cosine(x, y, a == b)
+    }
+}
+
+// Vectors are unit length, or empty if a string has no terms
+fn cosine(x: &SparseVector, y: &SparseVector, identical: bool) -> f64 {
+    if x.is_empty() || y.is_empty() {
+        return if identical { 1.0 } else { 0.0 };
+    }
+
+    let (mut i, mut j, mut dot) = (0, 0, 0.0);
+    while i < x.len() && j < y.len() {
+        match x[i].0.cmp(&y[j].0) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                dot += x[i].1 * y[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    dot.min(1.0)
+}
+
+impl Metric for TfIdfCosine {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+
+    fn fit(&mut self, inputs: &[&str]) {
+        let mut vocabulary = HashMap::new();
+        let mut document_frequencies = Vec::new();
+
+        for s in inputs {
+            let mut terms = self.terms.extract(s);
+            terms.sort_unstable();
+            terms.dedup();
+            for term in terms {
+                let next_id = vocabulary.len();
+                let id = *vocabulary.entry(term).or_insert(next_id);
+                if id == document_frequencies.len() {
+                    document_frequencies.push(0);
+                }
+                document_frequencies[id] += 1;
+            }
+        }
+
+        self.vocabulary = vocabulary;
+        self.n_documents = inputs.len();
+        self.idf = document_frequencies
+            .into_iter()
+            .map(|df| self.idf(df))
+            .collect();
+
+        let mut unseen = HashMap::new();
+        self.vectors = inputs
+            .iter()
+            .map(|s| (s.to_string(), self.vectorize(s, &mut unseen)))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TfIdfCosine;
+    use crate::metric::testing::assert_close;
+    use crate::metric::{Metric, Tokenizer};
+    use crate::ValueError;
+
+    #[test]
+    fn new_rejects_invalid_threshold() {
+        assert!(matches!(
+            TfIdfCosine::new(1.5),
+            Err(ValueError::InvalidFraction)
+        ));
+    }
+
+    #[test]
+    fn with_char_grams_rejects_zero() {
+        let result = TfIdfCosine::new(0.5).unwrap().with_char_grams(0);
+        assert!(matches!(result, Err(ValueError::InvalidGramSize)));
+    }
+
+    #[test]
+    fn unfitted_metric_weights_terms_equally() {
+        let metric = TfIdfCosine::new(0.0).unwrap();
+        assert_close(metric.similarity("a b", "a c"), 0.5);
+        assert_close(metric.similarity("a a", "a"), 1.0);
+    }
+
+    #[test]
+    fn fitted_weights_favor_rare_terms() {
+        let mut metric = TfIdfCosine::new(0.0).unwrap();
+        metric.fit(&["the red", "the blue", "the green", "the red car"]);
+        let common = metric.similarity("the red", "the blue");
+        let rare = metric.similarity("the red", "the red car");
+        assert!(common < 0.5, "{}", common);
+        assert!(rare > 0.5, "{}", rare);
+    }
+
+    #[test]
+    fn idf_smoothed() {
+        let mut metric = TfIdfCosine::new(0.0).unwrap();
+        metric.fit(&["a b", "a c"]);
+        // idf(a) = ln(3/3) + 1 = 1, idf(b) = idf(c) = ln(3/2) + 1
+        let w = (1.5f64).ln() + 1.0;
+        assert_close(metric.similarity("a b", "a c"), 1.0 / (1.0 + w * w));
+    }
+
+    #[test]
+    fn unseen_terms_compared() {
+        let mut metric = TfIdfCosine::new(0.0).unwrap();
+        metric.fit(&["a b"]);
+        assert_close(metric.similarity("x y", "x y"), 1.0);
+        assert_close(metric.similarity("a x", "b y"), 0.0);
+    }
+
+    #[test]
+    fn char_grams_applied() {
+        let metric = TfIdfCosine::new(0.0).unwrap().with_char_grams(2).unwrap();
+        assert_close(metric.similarity("abc", "abd"), 0.5);
+        assert_close(metric.similarity("a", "a"), 1.0);
+    }
+
+    #[test]
+    fn tokenizer_applied() {
+        let metric = TfIdfCosine::new(0.0)
+            .unwrap()
+            .with_tokenizer(Tokenizer::Alphanumeric);
+        assert_close(metric.similarity("acme,corp", "corp acme"), 1.0);
+    }
+
+    #[test]
+    fn handles_empty() {
+        let metric = TfIdfCosine::new(1.0).unwrap();
+        assert!(metric.is_similar("", ""));
+        assert!(!metric.is_similar("", "a"));
+    }
+
+    #[test]
+    fn distance_complements_similarity() {
+        let metric = TfIdfCosine::new(0.0).unwrap();
+        assert_close(metric.distance("a b", "a c"), 0.5);
+    }
+}
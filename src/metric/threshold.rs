@@ -0,0 +1,262 @@
+use crate::ValueError;
+
+pub fn check_fraction(frac: f32) -> Result<(), ValueError> {
+    if !(0.0..=1.0).contains(&frac) {
+        return Err(ValueError::InvalidFraction);
+    }
+    Ok(())
+}
+
+/// Maximum number of edits allowed between two similar strings.
+///
+/// Fractional thresholds are multiplied by a length derived from the pair of
+/// strings being compared and rounded down.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EditThreshold {
+    /// Fraction of the shorter string's length.
+    FractionOfShorter(f32),
+    /// Fraction of the longer string's length.
+    FractionOfLonger(f32),
+    /// Fraction of the mean of both strings' lengths.
+    FractionOfMean(f32),
+    /// Fixed number of edits, independent of string length.
+    Absolute(usize),
+}
+
+impl EditThreshold {
+    fn validate(&self) -> Result<(), ValueError> {
+        match *self {
+            EditThreshold::FractionOfShorter(frac)
+            | EditThreshold::FractionOfLonger(frac)
+            | EditThreshold::FractionOfMean(frac) => check_fraction(frac),
+            EditThreshold::Absolute(_) => Ok(()),
+        }
+    }
+
+    fn max_edits(&self, len_a: usize, len_b: usize) -> usize {
+        match *self {
+            EditThreshold::FractionOfShorter(frac) => get_max_edit_dist(len_a, len_b, frac),
+            EditThreshold::FractionOfLonger(frac) => (len_a.max(len_b) as f32 * frac) as usize,
+            EditThreshold::FractionOfMean(frac) => ((len_a + len_b) as f32 / 2.0 * frac) as usize,
+            EditThreshold::Absolute(max_edits) => max_edits,
+        }
+    }
+}
+
+// Threshold shared by the edit distance metrics, with the resulting maximum
+// edit distance clamped to [min_edits, max_edits]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EditLimit {
+    threshold: EditThreshold,
+    min_edits: usize,
+    max_edits: usize,
+}
+
+impl EditLimit {
+    pub fn new(threshold: EditThreshold) -> Result<EditLimit, ValueError> {
+        threshold.validate()?;
+        Ok(EditLimit {
+            threshold,
+            min_edits: 0,
+            max_edits: usize::MAX,
+        })
+    }
+
+    pub fn with_threshold(self, threshold: EditThreshold) -> Result<EditLimit, ValueError> {
+        threshold.validate()?;
+        Ok(EditLimit { threshold, ..self })
+    }
+
+    pub fn with_bounds(self, min_edits: usize, max_edits: usize) -> Result<EditLimit, ValueError> {
+        if min_edits > max_edits {
+            return Err(ValueError::InvalidEditBounds);
+        }
+        Ok(EditLimit {
+            min_edits,
+            max_edits,
+            ..self
+        })
+    }
+
+    pub fn max_edits(&self, len_a: usize, len_b: usize) -> usize {
+        self.threshold
+            .max_edits(len_a, len_b)
+            .clamp(self.min_edits, self.max_edits)
+    }
+}
+
+// Lengths must be measured in the same unit as the edit distance. The distance
+// function receives the maximum edit distance and may return any larger value
+// once the distance is known to exceed it.
+pub fn within_max_edit<F>(len_a: usize, len_b: usize, limit: &EditLimit, distance: F) -> bool
+where
+    F: FnOnce(usize) -> usize,
+{
+    let max_edit = limit.max_edits(len_a, len_b);
+    let len_diff = len_a.abs_diff(len_b);
+
+    // If difference in length between strings is greater than max
+    // edit distance it is not possible for the strings to be similar
+    if len_diff > max_edit {
+        return false;
+    }
+
+    let dist = distance(max_edit);
+    dist <= max_edit
+}
+
+fn get_max_edit_dist(len_a: usize, len_b: usize, tol: f32) -> usize {
+    let l_a = len_a as f32;
+    let l_b = len_b as f32;
+
+    // fraction of shorter string's length
+    let max_edit = if l_a < l_b { l_a * tol } else { l_b * tol };
+    max_edit as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_fraction, get_max_edit_dist, within_max_edit, EditLimit, EditThreshold};
+
+    fn shorter(frac: f32) -> EditLimit {
+        EditLimit::new(EditThreshold::FractionOfShorter(frac)).unwrap()
+    }
+
+    mod check_fraction {
+        use super::check_fraction;
+        use crate::ValueError;
+
+        #[test]
+        fn accepts_closed_interval() {
+            assert_eq!(check_fraction(0.0), Ok(()));
+            assert_eq!(check_fraction(1.0), Ok(()));
+        }
+
+        #[test]
+        fn rejects_outside_interval() {
+            assert_eq!(check_fraction(-0.1), Err(ValueError::InvalidFraction));
+            assert_eq!(check_fraction(1.1), Err(ValueError::InvalidFraction));
+        }
+    }
+
+    mod within_max_edit {
+        use super::{shorter, within_max_edit};
+
+        #[test]
+        fn distance_skipped_when_length_difference_too_large() {
+            let result = within_max_edit(1, 4, &shorter(1.0), |_| unreachable!());
+            assert!(!result);
+        }
+
+        #[test]
+        fn distance_receives_max_edit() {
+            assert!(within_max_edit(4, 4, &shorter(0.5), |max_edit| max_edit));
+        }
+
+        #[test]
+        fn distance_compared_with_max_edit() {
+            assert!(within_max_edit(4, 4, &shorter(0.5), |_| 2));
+            assert!(!within_max_edit(4, 4, &shorter(0.5), |_| 3));
+        }
+    }
+
+    mod max_edit_distance {
+        use super::get_max_edit_dist;
+
+        #[test]
+        fn max_edit_calculation_correct() {
+            let a = "aa";
+            let b = "bbb";
+            let result = get_max_edit_dist(a.chars().count(), b.chars().count(), 0.5);
+            assert_eq!(result, 1);
+        }
+
+        #[test]
+        fn max_edit_calculation_applies_floor() {
+            let a = "aaaa";
+            let b = "bbbb";
+            let result = get_max_edit_dist(a.chars().count(), b.chars().count(), 0.49);
+            assert_eq!(result, 1);
+        }
+
+        #[test]
+        fn max_edit_shorted_string_selected() {
+            let a = "aa";
+            let b = "bbb";
+            let op_one = get_max_edit_dist(a.chars().count(), b.chars().count(), 0.5);
+            let op_two = get_max_edit_dist(b.chars().count(), a.chars().count(), 0.5);
+            assert_eq!(op_one, op_two);
+        }
+
+        #[test]
+        fn max_edit_handles_empty() {
+            let a = "";
+            let b = "bbb";
+            let result = get_max_edit_dist(a.chars().count(), b.chars().count(), 0.5);
+            assert_eq!(result, 0);
+        }
+    }
+
+    mod edit_threshold {
+        use super::EditThreshold;
+
+        #[test]
+        fn longer_string_selected() {
+            let result = EditThreshold::FractionOfLonger(0.5).max_edits(2, 5);
+            assert_eq!(result, 2);
+        }
+
+        #[test]
+        fn mean_length_selected() {
+            let result = EditThreshold::FractionOfMean(0.5).max_edits(2, 6);
+            assert_eq!(result, 2);
+        }
+
+        #[test]
+        fn absolute_ignores_length() {
+            assert_eq!(EditThreshold::Absolute(3).max_edits(1, 100), 3);
+        }
+
+        #[test]
+        fn shorter_is_default_policy() {
+            let result = EditThreshold::FractionOfShorter(0.5).max_edits(2, 5);
+            assert_eq!(result, 1);
+        }
+    }
+
+    mod edit_limit {
+        use super::{shorter, EditLimit, EditThreshold};
+        use crate::ValueError;
+
+        #[test]
+        fn invalid_fraction_rejected() {
+            let result = EditLimit::new(EditThreshold::FractionOfMean(1.5));
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+            let result = shorter(0.5).with_threshold(EditThreshold::FractionOfLonger(-0.5));
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn inverted_bounds_rejected() {
+            let result = shorter(0.5).with_bounds(3, 2);
+            assert_eq!(result, Err(ValueError::InvalidEditBounds));
+        }
+
+        #[test]
+        fn minimum_bound_applied() {
+            let limit = shorter(0.25).with_bounds(1, 10).unwrap();
+            assert_eq!(limit.max_edits(2, 2), 1);
+        }
+
+        #[test]
+        fn maximum_bound_applied() {
+            let limit = shorter(0.5).with_bounds(0, 2).unwrap();
+            assert_eq!(limit.max_edits(20, 20), 2);
+        }
+
+        #[test]
+        fn unbounded_by_default() {
+            assert_eq!(shorter(1.0).max_edits(1000, 1000), 1000);
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use super::kernel::lcs_length;
+use super::threshold::check_fraction;
+use super::Metric;
+use crate::ValueError;
+
+use std::collections::BTreeSet;
+
+/// Splits strings into tokens for token-based metrics.
+#[derive(Clone, Copy, Debug)]
+pub enum Tokenizer {
+    /// Split on whitespace.
+    Whitespace,
+    /// Split on any character that is not alphanumeric.
+    Alphanumeric,
+    /// Split using a custom function.
+    Custom(fn(&str) -> Vec<&str>),
+}
+
+impl Tokenizer {
+    pub(crate) fn tokenize<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        match self {
+            Tokenizer::Whitespace => s.split_whitespace().collect(),
+            Tokenizer::Alphanumeric => s
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            Tokenizer::Custom(f) => f(s),
+        }
+    }
+}
+
+/// Token sort ratio thresholded on a minimum similarity.
+///
+/// Tokens are sorted and rejoined with single spaces before the strings are
+/// compared, so strings are similar regardless of word order. The similarity
+/// is twice the longest common subsequence of the rejoined strings divided by
+/// their combined length.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenSortRatio {
+    threshold: f32,
+    tokenizer: Tokenizer,
+}
+
+impl TokenSortRatio {
+    /// Create a new metric splitting tokens on whitespace.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<TokenSortRatio, ValueError> {
+        check_fraction(threshold)?;
+        Ok(TokenSortRatio {
+            threshold,
+            tokenizer: Tokenizer::Whitespace,
+        })
+    }
+
+    /// Set the tokenizer.
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> TokenSortRatio {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let mut a = self.tokenizer.tokenize(a);
+        let mut b = self.tokenizer.tokenize(b);
+        a.sort_unstable();
+        b.sort_unstable();
+        ratio(&a.join(" "), &b.join(" "))
+    }
+}
+
+impl Metric for TokenSortRatio {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+/// Token set ratio thresholded on a minimum similarity.
+///
+/// The distinct tokens shared by both strings are compared against each
+/// string's full token set, so strings are similar regardless of word order
+/// and of extra tokens in either string. The similarity is the best
+/// [`TokenSortRatio`]-style ratio among these comparisons.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenSetRatio {
+    threshold: f32,
+    tokenizer: Tokenizer,
+}
+
+impl TokenSetRatio {
+    /// Create a new metric splitting tokens on whitespace.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `threshold` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(threshold: f32) -> Result<TokenSetRatio, ValueError> {
+        check_fraction(threshold)?;
+        Ok(TokenSetRatio {
+            threshold,
+            tokenizer: Tokenizer::Whitespace,
+        })
+    }
+
+    /// Set the tokenizer.
+    pub fn with_tokenizer(mut self, tokenizer: Tokenizer) -> TokenSetRatio {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        // Ordered sets yield sorted tokens
+        let a: BTreeSet<&str> = self.tokenizer.tokenize(a).into_iter().collect();
+        let b: BTreeSet<&str> = self.tokenizer.tokenize(b).into_iter().collect();
+
+        // An empty set is a subset of every set, which would otherwise make it
+        // fully similar to anything
+        match (a.is_empty(), b.is_empty()) {
+            (true, true) => return 1.0,
+            (true, false) | (false, true) => return 0.0,
+            (false, false) => {}
+        }
+
+        let shared = join(a.intersection(&b));
+        let only_a = join(a.difference(&b));
+        let only_b = join(b.difference(&a));
+
+        let combined_a = join([&shared, &only_a].into_iter().filter(|s| !s.is_empty()));
+        let combined_b = join([&shared, &only_b].into_iter().filter(|s| !s.is_empty()));
+
+        ratio(&shared, &combined_a)
+            .max(ratio(&shared, &combined_b))
+            .max(ratio(&combined_a, &combined_b))
+    }
+}
+
+impl Metric for TokenSetRatio {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.similarity(a, b) >= self.threshold as f64
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        1.0 - self.similarity(a, b)
+    }
+}
+
+fn join<'a, I, S>(tokens: I) -> String
+where
+    I: Iterator<Item = &'a S>,
+    S: AsRef<str> + ?Sized + 'a,
+{
+    tokens.map(|t| t.as_ref()).collect::<Vec<&str>>().join(" ")
+}
+
+// Twice the longest common subsequence over the combined length
+fn ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    (2 * lcs_length(&a, &b)) as f64 / (a.len() + b.len()) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ratio, TokenSetRatio, TokenSortRatio, Tokenizer};
+    use crate::metric::testing::assert_close;
+    use crate::metric::Metric;
+    use crate::ValueError;
+
+    mod tokenizer {
+        use super::Tokenizer;
+
+        #[test]
+        fn whitespace_split() {
+            let result = Tokenizer::Whitespace.tokenize(" Acme  Corp,Ltd ");
+            assert_eq!(result, vec!["Acme", "Corp,Ltd"]);
+        }
+
+        #[test]
+        fn alphanumeric_split() {
+            let result = Tokenizer::Alphanumeric.tokenize(" Acme  Corp,Ltd ");
+            assert_eq!(result, vec!["Acme", "Corp", "Ltd"]);
+        }
+
+        #[test]
+        fn custom_split() {
+            let tokenizer = Tokenizer::Custom(|s| s.split('|').collect());
+            assert_eq!(tokenizer.tokenize("a|b c"), vec!["a", "b c"]);
+        }
+    }
+
+    mod ratio {
+        use super::{assert_close, ratio};
+
+        #[test]
+        fn ratio_correct() {
+            assert_close(ratio("abcd", "abce"), 0.75);
+        }
+
+        #[test]
+        fn handles_empty() {
+            assert_close(ratio("", ""), 1.0);
+            assert_close(ratio("", "abc"), 0.0);
+        }
+    }
+
+    mod token_sort_ratio {
+        use super::{assert_close, Metric, TokenSortRatio, Tokenizer, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert!(matches!(
+                TokenSortRatio::new(1.1),
+                Err(ValueError::InvalidFraction)
+            ));
+        }
+
+        #[test]
+        fn word_order_ignored() {
+            let metric = TokenSortRatio::new(1.0).unwrap();
+            assert_close(metric.similarity("Acme Corp Ltd", "Ltd Acme Corp"), 1.0);
+        }
+
+        #[test]
+        fn extra_tokens_penalised() {
+            let metric = TokenSortRatio::new(1.0).unwrap();
+            assert_close(metric.similarity("Acme Ltd", "Acme Corp Ltd"), 16.0 / 21.0);
+        }
+
+        #[test]
+        fn tokenizer_applied() {
+            let metric = TokenSortRatio::new(1.0)
+                .unwrap()
+                .with_tokenizer(Tokenizer::Alphanumeric);
+            assert!(metric.is_similar("Acme,Corp", "Corp Acme"));
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = TokenSortRatio::new(0.0).unwrap();
+            assert_close(metric.distance("b a", "a b"), 0.0);
+        }
+    }
+
+    mod token_set_ratio {
+        use super::{assert_close, Metric, TokenSetRatio, Tokenizer, ValueError};
+
+        #[test]
+        fn new_rejects_invalid_threshold() {
+            assert!(matches!(
+                TokenSetRatio::new(-0.1),
+                Err(ValueError::InvalidFraction)
+            ));
+        }
+
+        #[test]
+        fn extra_tokens_ignored() {
+            let metric = TokenSetRatio::new(1.0).unwrap();
+            assert_close(metric.similarity("Acme Ltd", "Ltd Acme Corp"), 1.0);
+        }
+
+        #[test]
+        fn no_shared_tokens_compares_full_sets() {
+            let metric = TokenSetRatio::new(1.0).unwrap();
+            assert_close(metric.similarity("abcd", "abce"), 0.75);
+        }
+
+        #[test]
+        fn threshold_applied() {
+            let metric = TokenSetRatio::new(0.75).unwrap();
+            assert!(metric.is_similar("Acme Corp Ltd", "Ltd Acme Corporation"));
+            assert!(!metric.is_similar("Acme Corp Ltd", "Globex Inc"));
+        }
+
+        #[test]
+        fn one_empty_token_set_dissimilar() {
+            let metric = TokenSetRatio::new(0.0).unwrap();
+            assert_close(metric.similarity("", "acme corp"), 0.0);
+            assert_close(metric.similarity("acme corp", "   "), 0.0);
+            let metric = metric.with_tokenizer(Tokenizer::Alphanumeric);
+            assert_close(metric.similarity("???", "acme corp"), 0.0);
+            assert_close(metric.similarity("acme corp", "--- / ---"), 0.0);
+        }
+
+        #[test]
+        fn both_empty_token_sets_identical() {
+            let metric = TokenSetRatio::new(1.0).unwrap();
+            assert_close(metric.similarity("", "  "), 1.0);
+            let metric = metric.with_tokenizer(Tokenizer::Alphanumeric);
+            assert_close(metric.similarity("???", "--- / ---"), 1.0);
+        }
+
+        #[test]
+        fn distance_complements_similarity() {
+            let metric = TokenSetRatio::new(0.0).unwrap();
+            assert_close(metric.distance("Acme Corp", "Corp Acme Ltd"), 0.0);
+        }
+    }
+}
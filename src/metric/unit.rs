@@ -0,0 +1,96 @@
+use super::fold::{with_buffers, Buffers, Folding};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Unit in which edit distance metrics measure string lengths and edits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Unit {
+    /// Unicode scalar values. "é" written as a single code point is one unit,
+    /// while "e" followed by a combining accent is two.
+    #[default]
+    Char,
+    /// Extended grapheme clusters, i.e. user-perceived characters. Combining
+    /// sequences and multi-code-point emoji such as flags are one unit.
+    Grapheme,
+}
+
+thread_local! {
+    static SYMBOLS: Buffers<u32> = const { RefCell::new((Vec::new(), Vec::new())) };
+}
+
+impl Unit {
+    // Split both strings into comparable symbols, applying `folding` to each,
+    // and pass them to `f`. Graphemes are numbered consistently across the
+    // pair so that kernels only compare integers.
+    pub(crate) fn with_symbols<R>(
+        &self,
+        a: &str,
+        b: &str,
+        folding: &Folding,
+        f: impl FnOnce(&[u32], &[u32]) -> R,
+    ) -> R {
+        let fill = |x: &mut Vec<u32>, y: &mut Vec<u32>| match self {
+            Unit::Char => {
+                x.extend(folding.chars(a).map(u32::from));
+                y.extend(folding.chars(b).map(u32::from));
+            }
+            Unit::Grapheme => {
+                let mut ids = HashMap::new();
+                let mut intern = |g| {
+                    let next = ids.len() as u32;
+                    *ids.entry(g).or_insert(next)
+                };
+                x.extend(folding.graphemes(a).map(&mut intern));
+                y.extend(folding.graphemes(b).map(&mut intern));
+            }
+        };
+        with_buffers(&SYMBOLS, fill, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Folding, Unit};
+    use crate::metric::{Case, Whitespace};
+
+    #[test]
+    fn chars_counted_not_bytes() {
+        Unit::Char.with_symbols("日本", "café", &Folding::default(), |a, b| {
+            assert_eq!(a.len(), 2);
+            assert_eq!(b.len(), 4);
+        });
+    }
+
+    #[test]
+    fn combining_sequences_form_one_grapheme() {
+        Unit::Grapheme.with_symbols("e\u{301}", "🇿🇦", &Folding::default(), |a, b| {
+            assert_eq!(a.len(), 1);
+            assert_eq!(b.len(), 1);
+        });
+    }
+
+    #[test]
+    fn graphemes_numbered_consistently() {
+        Unit::Grapheme.with_symbols(
+            "🇿🇦a🇿🇦",
+            "a🇿🇦",
+            &Folding::default(),
+            |a, b| {
+                assert_eq!(a, [0, 1, 0]);
+                assert_eq!(b, [1, 0]);
+            },
+        );
+    }
+
+    #[test]
+    fn folding_applied() {
+        let folding = Folding {
+            case: Case::Insensitive,
+            whitespace: Whitespace::Ignored,
+        };
+        Unit::Char.with_symbols("A b", "ab", &folding, |a, b| assert_eq!(a, b));
+        Unit::Grapheme.with_symbols("É 🇿🇦", "é🇿🇦", &folding, |a, b| {
+            assert_eq!(a, b)
+        });
+    }
+}
@@ -0,0 +1,395 @@
+use super::config::{edit_config_builders, EditConfig};
+use super::kernel::weighted_levenshtein_distance;
+use super::Metric;
+use crate::ValueError;
+
+use std::collections::HashMap;
+
+const QWERTY_ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// Characters commonly confused by optical character recognition
+const OCR_CONFUSIONS: [(char, char); 16] = [
+    ('O', '0'),
+    ('o', '0'),
+    ('D', '0'),
+    ('Q', 'O'),
+    ('I', '1'),
+    ('l', '1'),
+    ('I', 'l'),
+    ('i', 'l'),
+    ('S', '5'),
+    ('s', '5'),
+    ('B', '8'),
+    ('Z', '2'),
+    ('G', '6'),
+    ('b', '6'),
+    ('c', 'e'),
+    ('u', 'v'),
+];
+
+/// Costs of substituting specific pairs of characters.
+///
+/// Costs are symmetric. Pairs that are not in the table are substituted at the
+/// metric's default substitution cost.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SubstitutionTable {
+    costs: HashMap<(char, char), f32>,
+}
+
+impl SubstitutionTable {
+    /// Create an empty table.
+    pub fn new() -> SubstitutionTable {
+        SubstitutionTable::default()
+    }
+
+    /// Table in which characters on adjacent QWERTY keys are substituted at `cost`.
+    /// Letters are adjacent regardless of case.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if `cost` is negative or not finite.
+    pub fn qwerty(cost: f32) -> Result<SubstitutionTable, ValueError> {
+        let rows: Vec<Vec<char>> = QWERTY_ROWS.iter().map(|r| r.chars().collect()).collect();
+        let mut table = SubstitutionTable::new();
+
+        for (r, row) in rows.iter().enumerate() {
+            for (i, &key) in row.iter().enumerate() {
+                if let Some(&next) = row.get(i + 1) {
+                    table = table.with_cost_ignoring_case(key, next, cost)?;
+                }
+                // Each row is offset to the right of the row above it
+                if let Some(below) = rows.get(r + 1) {
+                    for j in [i.wrapping_sub(1), i] {
+                        if let Some(&other) = below.get(j) {
+                            table = table.with_cost_ignoring_case(key, other, cost)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// Table in which characters commonly confused by optical character
+    /// recognition, such as 'O' and '0', are substituted at `cost`.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if `cost` is negative or not finite.
+    pub fn ocr(cost: f32) -> Result<SubstitutionTable, ValueError> {
+        let mut table = SubstitutionTable::new();
+        for (a, b) in OCR_CONFUSIONS {
+            table = table.with_cost(a, b, cost)?;
+        }
+        Ok(table)
+    }
+
+    /// Set the cost of substituting `a` for `b`, and `b` for `a`.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if `cost` is negative or not finite.
+    pub fn with_cost(
+        mut self,
+        a: char,
+        b: char,
+        cost: f32,
+    ) -> Result<SubstitutionTable, ValueError> {
+        check_cost(cost)?;
+        self.costs.insert((a, b), cost);
+        self.costs.insert((b, a), cost);
+        Ok(self)
+    }
+
+    fn with_cost_ignoring_case(
+        mut self,
+        a: char,
+        b: char,
+        cost: f32,
+    ) -> Result<SubstitutionTable, ValueError> {
+        for a in [a, a.to_ascii_uppercase()] {
+            for b in [b, b.to_ascii_uppercase()] {
+                self = self.with_cost(a, b, cost)?;
+            }
+        }
+        Ok(self)
+    }
+
+    fn get(&self, a: char, b: char) -> Option<f32> {
+        self.costs.get(&(a, b)).copied()
+    }
+}
+
+/// Levenshtein distance with custom edit costs, thresholded on a fraction of
+/// the shorter string's length.
+///
+/// Strings are similar if the cheapest sequence of edits turning one string
+/// into the other costs at most `max_edit_frac` of the shorter string's length
+/// in characters (rounded down). All operations cost one by default, which
+/// matches [`Levenshtein`](super::Levenshtein). Cheaper substitutions for
+/// likely errors, such as typos on adjacent keys, can be configured with a
+/// [`SubstitutionTable`]. With case handling configured, costs are looked up
+/// for the case folded characters.
+///
+/// Insertions add characters of the second string and deletions remove
+/// characters of the first. With unequal insertion and deletion costs, the
+/// cheaper of the two directions is used, so that the metric is symmetric and
+/// clusters do not depend on the order in which strings are compared.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedLevenshtein {
+    config: EditConfig,
+    insert: f32,
+    delete: f32,
+    substitute: f32,
+    table: SubstitutionTable,
+}
+
+impl WeightedLevenshtein {
+    /// Create a new metric in which all operations cost one.
+    ///
+    /// Returns [`ValueError::InvalidFraction`] if `max_edit_frac` is outside of
+    /// the closed interval \[0,1\].
+    pub fn new(max_edit_frac: f32) -> Result<WeightedLevenshtein, ValueError> {
+        Ok(WeightedLevenshtein {
+            config: EditConfig::new(max_edit_frac)?,
+            insert: 1.0,
+            delete: 1.0,
+            substitute: 1.0,
+            table: SubstitutionTable::new(),
+        })
+    }
+
+    /// Set the cost of each operation. Substitutions of pairs in the
+    /// substitution table use the table's cost instead.
+    ///
+    /// Returns [`ValueError::InvalidCost`] if any cost is negative or not finite.
+    pub fn with_costs(
+        mut self,
+        insert: f32,
+        delete: f32,
+        substitute: f32,
+    ) -> Result<WeightedLevenshtein, ValueError> {
+        check_cost(insert)?;
+        check_cost(delete)?;
+        check_cost(substitute)?;
+        self.insert = insert;
+        self.delete = delete;
+        self.substitute = substitute;
+        Ok(self)
+    }
+
+    /// Set the costs of substituting specific pairs of characters.
+    pub fn with_substitution_table(mut self, table: SubstitutionTable) -> WeightedLevenshtein {
+        self.table = table;
+        self
+    }
+
+    // Turning `b` into `a` is turning `a` into `b` with the insertion and
+    // deletion costs swapped, as substitution costs are symmetric
+    fn cost(&self, a: &[char], b: &[char]) -> f64 {
+        let cost = |insert: f32, delete: f32| {
+            weighted_levenshtein_distance(a, b, insert as f64, delete as f64, |x, y| {
+                self.table.get(*x, *y).unwrap_or(self.substitute) as f64
+            })
+        };
+        if self.insert == self.delete {
+            cost(self.insert, self.delete)
+        } else {
+            cost(self.insert, self.delete).min(cost(self.delete, self.insert))
+        }
+    }
+}
+
+edit_config_builders!(WeightedLevenshtein, "total cost");
+
+impl Metric for WeightedLevenshtein {
+    fn is_similar(&self, a: &str, b: &str) -> bool {
+        self.config.folding.with_chars(a, b, |a, b| {
+            let max_edit = self.config.limit.max_edits(a.len(), b.len()) as f64;
+
+            // Every surplus character must be inserted or deleted
+            let indel = self.insert.min(self.delete);
+            if a.len().abs_diff(b.len()) as f64 * indel as f64 > max_edit {
+                return false;
+            }
+            self.cost(a, b) <= max_edit
+        })
+    }
+
+    fn distance(&self, a: &str, b: &str) -> f64 {
+        self.config.folding.with_chars(a, b, |a, b| self.cost(a, b))
+    }
+}
+
+fn check_cost(cost: f32) -> Result<(), ValueError> {
+    if !cost.is_finite() || cost < 0.0 {
+        return Err(ValueError::InvalidCost);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SubstitutionTable, WeightedLevenshtein};
+    use crate::metric::{EditThreshold, Metric};
+    use crate::ValueError;
+
+    mod substitution_table {
+        use super::{SubstitutionTable, ValueError};
+
+        #[test]
+        fn costs_symmetric() {
+            let table = SubstitutionTable::new().with_cost('a', 'b', 0.5).unwrap();
+            assert_eq!(table.get('a', 'b'), Some(0.5));
+            assert_eq!(table.get('b', 'a'), Some(0.5));
+            assert_eq!(table.get('a', 'c'), None);
+        }
+
+        #[test]
+        fn invalid_cost_rejected() {
+            let result = SubstitutionTable::new().with_cost('a', 'b', -1.0);
+            assert_eq!(result, Err(ValueError::InvalidCost));
+            let result = SubstitutionTable::new().with_cost('a', 'b', f32::NAN);
+            assert_eq!(result, Err(ValueError::InvalidCost));
+        }
+
+        #[test]
+        fn qwerty_adjacent_keys_included() {
+            let table = SubstitutionTable::qwerty(0.5).unwrap();
+            for (a, b) in [
+                ('q', 'w'),
+                ('s', 'w'),
+                ('s', 'e'),
+                ('z', 'a'),
+                ('1', 'q'),
+                ('B', 'n'),
+            ] {
+                assert_eq!(table.get(a, b), Some(0.5), "{} {}", a, b);
+            }
+        }
+
+        #[test]
+        fn qwerty_distant_keys_excluded() {
+            let table = SubstitutionTable::qwerty(0.5).unwrap();
+            for (a, b) in [('q', 'p'), ('a', 'e'), ('z', 'd'), ('q', 'q')] {
+                assert_eq!(table.get(a, b), None, "{} {}", a, b);
+            }
+        }
+
+        #[test]
+        fn qwerty_rejects_invalid_cost() {
+            assert_eq!(
+                SubstitutionTable::qwerty(-0.5),
+                Err(ValueError::InvalidCost)
+            );
+        }
+
+        #[test]
+        fn ocr_confusions_included() {
+            let table = SubstitutionTable::ocr(0.25).unwrap();
+            assert_eq!(table.get('0', 'O'), Some(0.25));
+            assert_eq!(table.get('1', 'l'), Some(0.25));
+            assert_eq!(table.get('a', 'b'), None);
+        }
+    }
+
+    mod weighted_levenshtein {
+        use super::{EditThreshold, Metric, SubstitutionTable, ValueError, WeightedLevenshtein};
+        use crate::metric::{Case, Whitespace};
+
+        #[test]
+        fn new_rejects_invalid_fraction() {
+            let result = WeightedLevenshtein::new(1.1);
+            assert_eq!(result, Err(ValueError::InvalidFraction));
+        }
+
+        #[test]
+        fn invalid_costs_rejected() {
+            let result = WeightedLevenshtein::new(0.5)
+                .unwrap()
+                .with_costs(1.0, -1.0, 1.0);
+            assert_eq!(result, Err(ValueError::InvalidCost));
+        }
+
+        #[test]
+        fn unit_costs_match_levenshtein() {
+            let metric = WeightedLevenshtein::new(0.5).unwrap();
+            assert_eq!(metric.distance("kitten", "sitting"), 3.0);
+            assert!(metric.is_similar("aaaa", "aabb"));
+            assert!(!metric.is_similar("aaaa", "abbb"));
+        }
+
+        #[test]
+        fn substitution_table_applied() {
+            let table = SubstitutionTable::ocr(0.25).unwrap();
+            let metric = WeightedLevenshtein::new(0.25)
+                .unwrap()
+                .with_substitution_table(table);
+            assert_eq!(metric.distance("B0OK", "BOOK"), 0.25);
+            assert!(metric.is_similar("INV01CE", "INVOICE"));
+            assert!(!metric.is_similar("INVXYCE", "INVOICE"));
+        }
+
+        #[test]
+        fn qwerty_table_applied() {
+            let table = SubstitutionTable::qwerty(0.5).unwrap();
+            let metric = WeightedLevenshtein::new(0.2)
+                .unwrap()
+                .with_substitution_table(table);
+            // Two typos on adjacent keys cost a single edit
+            assert!(metric.is_similar("hrllp", "hello"));
+            assert!(!metric.is_similar("hxllz", "hello"));
+        }
+
+        #[test]
+        fn length_difference_pruned_with_indel_costs() {
+            let metric = WeightedLevenshtein::new(0.5)
+                .unwrap()
+                .with_costs(0.5, 1.0, 1.0)
+                .unwrap();
+            // Two insertions at half cost fit within one edit
+            assert!(metric.is_similar("ab", "abcd"));
+            assert!(metric.is_similar("abcd", "ab"));
+            assert!(!metric.is_similar("ab", "abcdef"));
+        }
+
+        #[test]
+        fn unequal_indel_costs_symmetric() {
+            let metric = WeightedLevenshtein::new(0.25)
+                .unwrap()
+                .with_costs(1.0, 0.2, 1.0)
+                .unwrap();
+            assert!(metric.is_similar("abcd", "abcdxy"));
+            assert!(metric.is_similar("abcdxy", "abcd"));
+            assert_eq!(
+                metric.distance("kitten", "sitting"),
+                metric.distance("sitting", "kitten")
+            );
+        }
+
+        #[test]
+        fn threshold_policy_applied() {
+            let metric = WeightedLevenshtein::new(0.0)
+                .unwrap()
+                .with_threshold(EditThreshold::Absolute(2))
+                .unwrap();
+            assert!(metric.is_similar("a", "abc"));
+        }
+
+        #[test]
+        fn case_and_whitespace_handling_applied() {
+            let table = SubstitutionTable::new().with_cost('o', '0', 0.5).unwrap();
+            let metric = WeightedLevenshtein::new(0.0)
+                .unwrap()
+                .with_substitution_table(table)
+                .with_case(Case::InsensitiveAscii)
+                .with_whitespace(Whitespace::Ignored);
+            assert_eq!(metric.distance("B0 OK", "book"), 0.5);
+        }
+
+        #[test]
+        fn distance_sums_costs() {
+            let metric = WeightedLevenshtein::new(0.0)
+                .unwrap()
+                .with_costs(0.5, 2.0, 1.0)
+                .unwrap();
+            assert_eq!(metric.distance("ab", "abcd"), 1.0);
+            assert_eq!(metric.distance("abcd", "ab"), 1.0);
+            assert_eq!(metric.distance("ab", "xy"), 2.0);
+        }
+    }
+}
@@ -1,5 +1,13 @@
 pub mod aggregation;
+pub mod assignment;
+pub mod components;
+pub mod distances;
 pub mod formation;
+pub mod neighbourhoods;
+pub mod rows;
+pub mod scoring;
 
 pub use crate::clustering::cluster::cluster;
+pub use crate::clustering::disjoint_set::DisjointSet;
+pub use crate::clustering::distance_matrix::DistanceMatrix;
 pub use crate::clustering::merge::merge_clusters;
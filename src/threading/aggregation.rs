@@ -1,13 +1,15 @@
 use super::merge_clusters;
+use crate::metric::Metric;
 
 use crossbeam;
 use fast_math::log2_raw;
 use std::sync::{Arc, Mutex};
 
-pub fn aggregate_results<'a>(
-    results: Vec<Vec<Vec<&'a str>>>,
-    max_edit_frac: f32,
-) -> Vec<Vec<&'a str>> {
+pub fn aggregate_results<'a, S, M>(results: Vec<Vec<Vec<&'a S>>>, metric: &M) -> Vec<Vec<&'a S>>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
     let mut results = Arc::new(results);
     let n_aggregations = log2_raw(results.len() as f32).ceil() as usize;
 
@@ -28,7 +30,7 @@ pub fn aggregate_results<'a>(
                     } else {
                         // Merge clusters
                         let y = &mut results[j + 1].clone();
-                        let agg = merge_clusters(x, y, max_edit_frac);
+                        let agg = merge_clusters(x, y, metric);
                         aggregations.lock().unwrap().push(agg);
                     }
                 });
@@ -39,18 +41,19 @@ pub fn aggregate_results<'a>(
         results = Arc::new(aggregations.lock().unwrap().clone());
         aggregations.lock().unwrap().clear();
     }
-    return results.to_vec()[0].clone();
+    results.to_vec()[0].clone()
 }
 
 #[cfg(test)]
 mod tests {
     use super::aggregate_results;
+    use crate::metric::Levenshtein;
 
     #[test]
     fn test_one_merge() {
         let input = vec![vec![vec!["aa"], vec!["bb"]], vec![vec!["aa"], vec!["bb"]]];
         let expected = vec![vec!["aa", "aa"], vec!["bb", "bb"]];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -65,7 +68,7 @@ mod tests {
             vec![vec!["bb"]],
         ];
         let expected = vec![vec!["aa", "aa"], vec!["bb", "bb"]];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -75,7 +78,7 @@ mod tests {
     fn test_two_merge_one_pass() {
         let input = vec![vec![vec!["aa"]], vec![vec!["bb"]], vec![vec!["aa"]]];
         let expected = vec![vec!["aa", "aa"], vec!["bb"]];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -93,7 +96,7 @@ mod tests {
             vec![vec!["aa"]],
         ];
         let expected = vec![vec!["aa", "aa", "aa", "aa"], vec!["bb", "bb", "bb"]];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -111,7 +114,7 @@ mod tests {
             vec!["bbb", "bbb", "bbb", "bbb", "bbb", "bbb"],
             vec!["ccc", "ccc", "ccc", "ccc", "ccc", "ccc"],
         ];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -127,7 +130,7 @@ mod tests {
             vec!["aaaa", "aaaa", "aaax", "aaax"],
             vec!["bbbb", "bbbb", "bbbz", "bbbz"],
         ];
-        let results = aggregate_results(input, 0.25);
+        let results = aggregate_results(input, &Levenshtein::new(0.25).unwrap());
         for e in expected {
             assert!(results.contains(&e));
         }
@@ -137,7 +140,7 @@ mod tests {
     fn test_no_merge() {
         let input = vec![vec![vec!["aa", "aa"]]];
         let expected = vec![vec!["aa", "aa"]];
-        let results = aggregate_results(input, 0.0);
+        let results = aggregate_results(input, &Levenshtein::new(0.0).unwrap());
         assert_eq!(results, expected);
     }
 }
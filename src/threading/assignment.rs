@@ -0,0 +1,73 @@
+use crate::metric::Metric;
+
+// Index into `medoids` of the nearest medoid of every input, with the distance
+// to it. Medoids are assigned to themselves.
+pub fn assign_medoids<S, M>(
+    inputs: &[&S],
+    medoids: &[usize],
+    metric: &M,
+    n_threads: usize,
+) -> Vec<(usize, f64)>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let inputs_per_thread = inputs.len().div_ceil(n_threads).max(1);
+
+    crossbeam::scope(|s| {
+        let handles: Vec<_> = (0..inputs.len())
+            .step_by(inputs_per_thread)
+            .map(|start| {
+                let end = (start + inputs_per_thread).min(inputs.len());
+                s.spawn(move |_| {
+                    (start..end)
+                        .map(|i| nearest(inputs, medoids, metric, i))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        // Joining in spawn order preserves the order of the inputs
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+    .unwrap()
+}
+
+fn nearest<S, M>(inputs: &[&S], medoids: &[usize], metric: &M, i: usize) -> (usize, f64)
+where
+    S: ?Sized,
+    M: Metric<S>,
+{
+    if let Some(slot) = medoids.iter().position(|&m| m == i) {
+        return (slot, 0.0);
+    }
+
+    let mut best = (0, f64::INFINITY);
+    for (slot, &m) in medoids.iter().enumerate() {
+        let d = metric.distance(inputs[m], inputs[i]);
+        if d < best.1 {
+            best = (slot, d);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assign_medoids;
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn test_nearest_medoid_assigned() {
+        let data = vec!["aaaa", "aaab", "bbbb", "abbb", "bbbb"];
+        let expected = vec![(0, 0.0), (0, 1.0), (1, 0.0), (1, 1.0), (1, 0.0)];
+        let metric = Levenshtein::new(0.0).unwrap();
+        for n_threads in 1..=data.len() {
+            let result = assign_medoids(&data, &[0, 2], &metric, n_threads);
+            assert_eq!(result, expected);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::rows::deal_rows;
+use super::DisjointSet;
+use crate::metric::Metric;
+
+pub fn connected_components<'a, S, M>(
+    inputs: &[&'a S],
+    metric: &M,
+    n_threads: usize,
+) -> Vec<Vec<&'a S>>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let n = inputs.len();
+
+    let mut forests = deal_rows(n, n_threads, |rows| {
+        let mut forest = DisjointSet::new(n);
+        for i in rows {
+            for j in i + 1..n {
+                // Pairs already connected need not be compared
+                if forest.find(i) != forest.find(j) && metric.is_similar(inputs[i], inputs[j]) {
+                    forest.union(i, j);
+                }
+            }
+        }
+        forest
+    });
+
+    let mut components = forests.pop().expect("at least one thread");
+    for forest in forests.iter_mut() {
+        components.absorb(forest);
+    }
+
+    components
+        .sets()
+        .into_iter()
+        .map(|set| set.into_iter().map(|i| inputs[i]).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::connected_components;
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn test_chains_connected() {
+        let data = vec!["aaaa", "zzzz", "aabb", "abbb", "aaab"];
+        let expected = vec![vec!["aaaa", "aabb", "abbb", "aaab"], vec!["zzzz"]];
+        let result = connected_components(&data, &Levenshtein::new(0.25).unwrap(), 1);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_chains_connected_across_threads() {
+        let data = vec!["aaaa", "zzzz", "aabb", "abbb", "aaab", "bbbb", "zzzy"];
+        let expected = vec![
+            vec!["aaaa", "aabb", "abbb", "aaab", "bbbb"],
+            vec!["zzzz", "zzzy"],
+        ];
+        for n_threads in 1..=data.len() {
+            let result = connected_components(&data, &Levenshtein::new(0.25).unwrap(), n_threads);
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[test]
+    fn test_singletons_kept() {
+        let data = vec!["a", "b", "c"];
+        let expected = vec![vec!["a"], vec!["b"], vec!["c"]];
+        let result = connected_components(&data, &Levenshtein::new(0.0).unwrap(), 2);
+        assert_eq!(result, expected);
+    }
+}
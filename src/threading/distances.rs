@@ -0,0 +1,47 @@
+use super::rows::deal_rows;
+use super::DistanceMatrix;
+use crate::metric::Metric;
+
+pub fn pairwise_distances<S, M>(inputs: &[&S], metric: &M, n_threads: usize) -> DistanceMatrix
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let n = inputs.len();
+
+    let rows = deal_rows(n, n_threads, |rows| {
+        rows.map(|i| {
+            let row: Vec<f64> = (i + 1..n)
+                .map(|j| metric.distance(inputs[i], inputs[j]))
+                .collect();
+            (i, row)
+        })
+        .collect::<Vec<_>>()
+    });
+
+    let mut distances = vec![0.0; n * n.saturating_sub(1) / 2];
+    for (i, row) in rows.into_iter().flatten() {
+        let start = DistanceMatrix::row_start(n, i);
+        distances[start..start + row.len()].copy_from_slice(&row);
+    }
+    DistanceMatrix::new(n, distances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pairwise_distances;
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn test_all_pairs_measured() {
+        let data = vec!["a", "ab", "abc", "abcd", "x"];
+        let metric = Levenshtein::new(0.0).unwrap();
+        for n_threads in 1..=data.len() {
+            let matrix = pairwise_distances(&data, &metric, n_threads);
+            assert_eq!(matrix.get(0, 3), 3.0);
+            assert_eq!(matrix.get(1, 2), 1.0);
+            assert_eq!(matrix.get(4, 3), 4.0);
+            assert_eq!(matrix.get(0, 4), 1.0);
+        }
+    }
+}
@@ -1,28 +1,31 @@
 use super::cluster;
+use crate::metric::Metric;
 use std::sync::{Arc, Mutex};
 
-pub fn form_clusters<'a>(
-    inputs: &'a Vec<&'a str>,
-    max_edit_frac: f32,
+pub fn form_clusters<'a, S, M>(
+    inputs: &'a [&'a S],
+    metric: &M,
     n_threads: usize,
-) -> Vec<Vec<Vec<&'a str>>> {
-    let inputs_per_thread = inputs.len() / n_threads as usize;
+) -> Vec<Vec<Vec<&'a S>>>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let inputs_per_thread = inputs.len() / n_threads;
     let results = Arc::new(Mutex::new(Vec::new()));
 
     crossbeam::scope(|s| {
         for i in 0..n_threads {
             let results = Arc::clone(&results);
             let start = i * inputs_per_thread;
-            let end;
-
-            if i + 1 != n_threads {
-                end = (i + 1) * inputs_per_thread;
+            let end = if i + 1 != n_threads {
+                (i + 1) * inputs_per_thread
             } else {
-                end = inputs.len();
-            }
+                inputs.len()
+            };
 
             s.spawn(move |_| {
-                let clusters = cluster(&inputs[start..end], max_edit_frac);
+                let clusters = cluster(&inputs[start..end], metric);
                 {
                     results.lock().unwrap().push(clusters);
                 }
@@ -36,12 +39,13 @@ pub fn form_clusters<'a>(
 #[cfg(test)]
 mod tests {
     use super::form_clusters;
+    use crate::metric::Levenshtein;
 
     #[test]
     fn test_correct_equal_work_per_thread() {
         let data = vec!["aa", "aa", "bb", "bb"];
         let expected = vec![vec![vec!["aa", "aa"]], vec![vec!["bb", "bb"]]];
-        let result = form_clusters(&data, 0.0, 2);
+        let result = form_clusters(&data, &Levenshtein::new(0.0).unwrap(), 2);
         // Order of objects in result is nondeterministic
         for e in expected {
             assert!(result.contains(&e))
@@ -52,7 +56,7 @@ mod tests {
     fn test_unequal_work_per_thread() {
         let data = vec!["aa", "aa", "bb", "bb"];
         let expected = vec![vec![vec!["aa"]], vec![vec!["aa"]], vec![vec!["bb", "bb"]]];
-        let result = form_clusters(&data, 0.0, 3);
+        let result = form_clusters(&data, &Levenshtein::new(0.0).unwrap(), 3);
         // Order of objects in result is nondeterministic
         for e in expected {
             assert!(result.contains(&e))
@@ -68,7 +72,7 @@ mod tests {
             vec![vec!["bb"]],
             vec![vec!["bb"]],
         ];
-        let result = form_clusters(&data, 0.0, 4);
+        let result = form_clusters(&data, &Levenshtein::new(0.0).unwrap(), 4);
         // Order of objects in result is nondeterministic
         for e in expected {
             assert!(result.contains(&e))
@@ -0,0 +1,49 @@
+use super::rows::deal_rows;
+use crate::metric::Metric;
+
+// Indices of the inputs similar to each input, in increasing order
+pub fn find_neighbours<S, M>(inputs: &[&S], metric: &M, n_threads: usize) -> Vec<Vec<usize>>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let n = inputs.len();
+
+    let edges = deal_rows(n, n_threads, |rows| {
+        let mut edges = Vec::new();
+        for i in rows {
+            for j in i + 1..n {
+                if metric.is_similar(inputs[i], inputs[j]) {
+                    edges.push((i, j));
+                }
+            }
+        }
+        edges
+    });
+
+    let mut neighbours = vec![Vec::new(); n];
+    for &(i, j) in edges.iter().flatten() {
+        neighbours[i].push(j);
+        neighbours[j].push(i);
+    }
+    for adjacent in neighbours.iter_mut() {
+        adjacent.sort_unstable();
+    }
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_neighbours;
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn test_neighbours_symmetric() {
+        let data = vec!["aa", "bb", "aa", "ab", "bb"];
+        let expected = vec![vec![2], vec![4], vec![0], vec![], vec![1]];
+        for n_threads in 1..=data.len() {
+            let result = find_neighbours(&data, &Levenshtein::new(0.0).unwrap(), n_threads);
+            assert_eq!(result, expected);
+        }
+    }
+}
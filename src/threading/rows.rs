@@ -0,0 +1,41 @@
+use std::iter::StepBy;
+use std::ops::Range;
+
+// Rows of the pairwise comparison triangle are dealt out in turn, so that
+// every thread receives long and short rows alike. Returns the result of
+// `work` on each thread's rows.
+pub fn deal_rows<T, F>(n: usize, n_threads: usize, work: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(StepBy<Range<usize>>) -> T + Sync,
+{
+    let work = &work;
+    crossbeam::scope(|s| {
+        let handles: Vec<_> = (0..n_threads)
+            .map(|t| s.spawn(move |_| work((t..n).step_by(n_threads))))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::deal_rows;
+
+    #[test]
+    fn test_rows_dealt_in_turn() {
+        let result = deal_rows(5, 2, |rows| rows.collect::<Vec<_>>());
+        assert_eq!(result, vec![vec![0, 2, 4], vec![1, 3]]);
+    }
+
+    #[test]
+    fn test_surplus_threads_idle() {
+        let result = deal_rows(2, 3, |rows| rows.count());
+        assert_eq!(result, vec![1, 1, 0]);
+    }
+}
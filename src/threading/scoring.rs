@@ -0,0 +1,77 @@
+use crate::metric::Metric;
+
+pub fn score_clusters<'a, S, M>(
+    clusters: &[Vec<&'a S>],
+    metric: &M,
+    n_threads: usize,
+) -> Vec<Vec<(&'a S, f64)>>
+where
+    S: ?Sized + Sync,
+    M: Metric<S>,
+{
+    let clusters_per_thread = clusters.len().div_ceil(n_threads).max(1);
+
+    crossbeam::scope(|s| {
+        let handles: Vec<_> = clusters
+            .chunks(clusters_per_thread)
+            .map(|chunk| {
+                s.spawn(move |_| {
+                    chunk
+                        .iter()
+                        .map(|cluster| score(cluster, metric))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        // Joining in spawn order preserves the order of the clusters
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+    .unwrap()
+}
+
+// Distance of each member to the cluster representative, its first member
+fn score<'a, S, M>(cluster: &[&'a S], metric: &M) -> Vec<(&'a S, f64)>
+where
+    S: ?Sized,
+    M: Metric<S>,
+{
+    let representative = cluster[0];
+    cluster
+        .iter()
+        .map(|&s| (s, metric.distance(representative, s)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::score_clusters;
+    use crate::metric::Levenshtein;
+
+    #[test]
+    fn test_distances_to_representative() {
+        let clusters = vec![vec!["aaaa", "aaab", "aabb"], vec!["cccc"]];
+        let expected = vec![
+            vec![("aaaa", 0.0), ("aaab", 1.0), ("aabb", 2.0)],
+            vec![("cccc", 0.0)],
+        ];
+        let results = score_clusters(&clusters, &Levenshtein::new(0.5).unwrap(), 1);
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_order_preserved_across_threads() {
+        let clusters = vec![vec!["a"], vec!["b", "bc"], vec!["c"], vec!["d", "e"]];
+        let expected = vec![
+            vec![("a", 0.0)],
+            vec![("b", 0.0), ("bc", 1.0)],
+            vec![("c", 0.0)],
+            vec![("d", 0.0), ("e", 1.0)],
+        ];
+        let results = score_clusters(&clusters, &Levenshtein::new(1.0).unwrap(), 3);
+        assert_eq!(results, expected);
+    }
+}
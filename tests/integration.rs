@@ -1,3 +1,5 @@
+#![allow(clippy::useless_vec)]
+
 #[test]
 fn test_basic_correctness() {
     let inputs = vec!["a", "b", "c", "a", "b", "c"];
@@ -115,3 +117,560 @@ fn test_output_consistent_across_thread_counts() {
         }
     }
 }
+
+#[test]
+fn test_custom_metric_applied() {
+    struct SameLength;
+
+    impl clustr::metric::Metric for SameLength {
+        fn is_similar(&self, a: &str, b: &str) -> bool {
+            a.len() == b.len()
+        }
+    }
+
+    let inputs = vec!["a", "bb", "c", "dd"];
+    let expected = vec![vec!["a", "c"], vec!["bb", "dd"]];
+    let results = clustr::cluster_strings_with_metric(&inputs, SameLength, 2).unwrap();
+    // cluster ordering nondeterministic
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+}
+
+#[test]
+fn test_metric_rejects_invalid_fraction() {
+    let expected = Err(clustr::ValueError::InvalidFraction);
+    let results = clustr::metric::Levenshtein::new(1.5);
+    assert_eq!(expected, results);
+}
+
+#[test]
+fn test_transposition_metrics_cluster_swaps() {
+    let inputs = vec!["abcd", "abdc", "wxyz", "wxzy"];
+    let expected = vec![vec!["abcd", "abdc"], vec!["wxyz", "wxzy"]];
+
+    let metric = clustr::metric::OptimalStringAlignment::new(0.25).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+
+    let metric = clustr::metric::DamerauLevenshtein::new(0.25).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_jaro_winkler_clusters_names() {
+    let inputs = vec!["martha", "marhta", "dwayne", "duane"];
+    let expected = vec![vec!["martha", "marhta"], vec!["dwayne", "duane"]];
+    let metric = clustr::metric::JaroWinkler::new(0.8).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_qgram_metrics_cluster_reordered_strings() {
+    let inputs = vec![
+        "black cotton shirt",
+        "cotton shirt black",
+        "red wool scarf",
+        "wool scarf red",
+    ];
+    let expected = vec![
+        vec!["black cotton shirt", "cotton shirt black"],
+        vec!["red wool scarf", "wool scarf red"],
+    ];
+
+    let metric = clustr::metric::Jaccard::new(0.6).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+
+    let metric = clustr::metric::SorensenDice::new(0.7).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_token_metrics_cluster_multi_word_names() {
+    let inputs = vec![
+        "Acme Corp Ltd",
+        "Globex Inc",
+        "Ltd Acme Corporation",
+        "Inc Globex",
+    ];
+    let expected = vec![
+        vec!["Acme Corp Ltd", "Ltd Acme Corporation"],
+        vec!["Globex Inc", "Inc Globex"],
+    ];
+
+    let metric = clustr::metric::TokenSortRatio::new(0.75).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+
+    let metric = clustr::metric::TokenSetRatio::new(0.75).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_multi_byte_strings_measured_in_chars() {
+    let inputs = vec!["日本", "中国", "café", "cafe"];
+    let expected = vec![vec!["日本"], vec!["中国"], vec!["café", "cafe"]];
+    let results = clustr::cluster_strings(&inputs, 0.5, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_absolute_threshold_clusters_short_strings() {
+    use clustr::metric::{EditThreshold, Levenshtein};
+
+    let inputs = vec!["ab", "ac", "xy", "xz"];
+    let expected = vec![vec!["ab", "ac"], vec!["xy", "xz"]];
+    let metric = Levenshtein::new(0.0)
+        .unwrap()
+        .with_threshold(EditThreshold::Absolute(1))
+        .unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_weighted_metric_clusters_ocr_errors() {
+    use clustr::metric::{SubstitutionTable, WeightedLevenshtein};
+
+    let inputs = vec!["INVOICE", "1NV01CE", "RECEIPT", "REC3IP7"];
+    let expected = vec![vec!["INVOICE", "1NV01CE"], vec!["RECEIPT"], vec!["REC3IP7"]];
+    let metric = WeightedLevenshtein::new(0.15)
+        .unwrap()
+        .with_substitution_table(SubstitutionTable::ocr(0.3).unwrap());
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_phonetic_metric_clusters_homophones() {
+    use clustr::metric::{Phonetic, PhoneticEncoder};
+
+    let inputs = vec!["Catherine", "Smith", "Kathryn", "Smyth", "Jones"];
+    let expected = vec![
+        vec!["Catherine", "Kathryn"],
+        vec!["Smith", "Smyth"],
+        vec!["Jones"],
+    ];
+    let metric = Phonetic::new(PhoneticEncoder::DoubleMetaphone);
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_normalized_metric_returns_original_strings() {
+    use clustr::metric::{Levenshtein, Normalized, Normalizer};
+
+    let inputs = vec!["Crème Brûlée", "creme  brulee", "Crème-Caramel"];
+    let expected = vec![vec!["Crème Brûlée", "creme  brulee"], vec!["Crème-Caramel"]];
+    let normalizer = Normalizer::new()
+        .with_case_folding()
+        .with_diacritics_stripped()
+        .with_whitespace_collapsed();
+    let metric = Normalized::new(normalizer, Levenshtein::new(0.0).unwrap());
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_longest_common_substring_clusters_shared_stems() {
+    let inputs = vec![
+        "reports/quarterly_summary_v1.pdf",
+        "reports/quarterly_summary_final.pdf",
+        "src/main.rs",
+    ];
+    let expected = vec![
+        vec![
+            "reports/quarterly_summary_v1.pdf",
+            "reports/quarterly_summary_final.pdf",
+        ],
+        vec!["src/main.rs"],
+    ];
+    let metric = clustr::metric::LongestCommonSubstring::new(0.7).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_hamming_metric_clusters_fixed_length_codes() {
+    use clustr::metric::Hamming;
+
+    let inputs = vec!["ACGTACGT", "ACGTACGA", "TTTTGGGG", "TTTTGGGC"];
+    let expected = vec![vec!["ACGTACGT", "ACGTACGA"], vec!["TTTTGGGG", "TTTTGGGC"]];
+    let metric = Hamming::new(0.125).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 2).unwrap();
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+}
+
+#[test]
+fn test_hamming_metric_rejects_unequal_lengths() {
+    use clustr::metric::{Hamming, LengthPolicy};
+
+    let inputs = vec!["ACGT", "ACG"];
+    let metric = Hamming::new(0.25).unwrap();
+    let result = clustr::cluster_strings_with_metric(&inputs, metric, 1);
+    assert_eq!(result, Err(clustr::ValueError::UnequalLengths));
+
+    let metric = metric.with_length_policy(LengthPolicy::Dissimilar);
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, vec![vec!["ACGT"], vec!["ACG"]]);
+}
+
+#[test]
+fn test_tfidf_metric_ignores_common_tokens() {
+    use clustr::metric::TfIdfCosine;
+
+    let inputs = vec![
+        "black cotton shirt",
+        "black leather boots",
+        "black cotton shirt xl",
+        "black wool hat",
+    ];
+    let expected = vec![
+        vec!["black cotton shirt", "black cotton shirt xl"],
+        vec!["black leather boots"],
+        vec!["black wool hat"],
+    ];
+    let metric = TfIdfCosine::new(0.5).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_fields_metric_clusters_records() {
+    use clustr::metric::{Combination, Fields, JaroWinkler, Levenshtein};
+
+    let inputs = vec![
+        "Jon | Smith | Leeds",
+        "John | Smyth | Leeds",
+        "John | Smith | York",
+        "Mary | Jones | York",
+    ];
+    let expected = vec![
+        vec!["Jon | Smith | Leeds", "John | Smyth | Leeds"],
+        vec!["John | Smith | York"],
+        vec!["Mary | Jones | York"],
+    ];
+    let metric = Fields::new("|")
+        .unwrap()
+        .with_weighted_field(JaroWinkler::new(0.85).unwrap(), 1.0)
+        .unwrap()
+        .with_weighted_field(Levenshtein::new(0.2).unwrap(), 2.0)
+        .unwrap()
+        .with_weighted_field(Levenshtein::new(0.0).unwrap(), 2.0)
+        .unwrap()
+        .with_combination(Combination::WeightedVote(0.8))
+        .unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_cluster_sequences_of_token_ids() {
+    let inputs: Vec<&[u32]> = vec![&[1, 2, 3, 4], &[9, 9, 9], &[1, 2, 3, 5], &[9, 9, 8]];
+    let expected: Vec<Vec<&[u32]>> = vec![
+        vec![&[1, 2, 3, 4], &[1, 2, 3, 5]],
+        vec![&[9, 9, 9], &[9, 9, 8]],
+    ];
+    let results = clustr::cluster_sequences(&inputs, 0.34, 2).unwrap();
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+}
+
+#[test]
+fn test_cluster_sequences_validates_inputs() {
+    let inputs: Vec<&[u8]> = vec![];
+    let result = clustr::cluster_sequences(&inputs, 0.5, 1);
+    assert_eq!(result, Err(clustr::ValueError::EmptyVector));
+
+    let inputs: Vec<&[u8]> = vec![b"ACGT", b"ACG"];
+    let metric = clustr::metric::Hamming::new(0.25).unwrap();
+    let result = clustr::cluster_sequences_with_metric(&inputs, metric, 1);
+    assert_eq!(result, Err(clustr::ValueError::UnequalLengths));
+}
+
+#[test]
+fn test_fields_metric_clusters_pre_split_records() {
+    use clustr::metric::{Fields, Levenshtein};
+
+    let inputs: Vec<&[&str]> = vec![&["Jon", "Leeds"], &["John", "Leeds"], &["John", "York"]];
+    let expected: Vec<Vec<&[&str]>> = vec![
+        vec![&["Jon", "Leeds"], &["John", "Leeds"]],
+        vec![&["John", "York"]],
+    ];
+    let metric = Fields::new(",")
+        .unwrap()
+        .with_field(Levenshtein::new(0.34).unwrap())
+        .with_field(Levenshtein::new(0.0).unwrap());
+    let results = clustr::cluster_sequences_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_cluster_strings_with_distances_to_representative() {
+    let inputs = vec!["kitten", "sitten", "sittin", "zzzzzz"];
+    let metric = clustr::metric::Levenshtein::new(0.34).unwrap();
+    let results = clustr::cluster_strings_with_distances(&inputs, metric, 2).unwrap();
+    let expected = vec![
+        vec![("kitten", 0.0), ("sitten", 1.0), ("sittin", 2.0)],
+        vec![("zzzzzz", 0.0)],
+    ];
+    // cluster ordering nondeterministic
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+}
+
+#[test]
+fn test_alignment_metrics_tolerate_long_insertions() {
+    use clustr::metric::{NeedlemanWunsch, SmithWaterman};
+
+    let inputs = vec![
+        "ERROR disk full on /dev/sda1",
+        "2024-03-01 node-4 ERROR disk full on /dev/sda1 (retrying)",
+        "WARN slow response from upstream",
+        "2024-03-01 node-9 WARN slow response from upstream after 30s",
+    ];
+    let expected = vec![vec![inputs[0], inputs[1]], vec![inputs[2], inputs[3]]];
+    let metric = SmithWaterman::new(0.9).unwrap();
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 2).unwrap();
+    // cluster ordering nondeterministic
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+
+    let inputs: Vec<&[u8]> = vec![b"ACGTACGTACGT", b"ACGTACGTTTTTTACGT", b"TTGCATTGCA"];
+    let metric = NeedlemanWunsch::new(0.5)
+        .unwrap()
+        .with_gap_penalties(1.0, 0.1)
+        .unwrap();
+    let results = clustr::cluster_sequences_with_metric(&inputs, metric, 1).unwrap();
+    assert_eq!(results, vec![vec![inputs[0], inputs[1]], vec![inputs[2]]]);
+}
+
+#[test]
+fn test_case_and_whitespace_insensitive_metric_returns_originals() {
+    use clustr::metric::{Case, Levenshtein, Whitespace};
+
+    let inputs = vec!["ACME  Corp", "acme corp", "Globex", " GLOBEX "];
+    let expected = vec![vec!["ACME  Corp", "acme corp"], vec!["Globex", " GLOBEX "]];
+    let metric = Levenshtein::new(0.0)
+        .unwrap()
+        .with_case(Case::Insensitive)
+        .with_whitespace(Whitespace::Collapsed);
+    let results = clustr::cluster_strings_with_metric(&inputs, metric, 2).unwrap();
+    // cluster ordering nondeterministic
+    for e in expected {
+        assert!(results.contains(&e));
+    }
+}
+
+#[test]
+fn test_single_linkage_mode_connects_chains() {
+    use clustr::metric::Levenshtein;
+    use clustr::Mode;
+
+    let inputs = vec!["aaaa", "zzzz", "bbbb", "aabb", "zzzy", "abbb", "aaab"];
+    let expected = vec![
+        vec!["aaaa", "bbbb", "aabb", "abbb", "aaab"],
+        vec!["zzzz", "zzzy"],
+    ];
+    for n_threads in 1..=4 {
+        let metric = Levenshtein::new(0.25).unwrap();
+        let results =
+            clustr::cluster_strings_with_mode(&inputs, metric, Mode::SingleLinkage, n_threads)
+                .unwrap();
+        assert_eq!(results, expected);
+    }
+}
+
+#[test]
+fn test_single_linkage_mode_validates_inputs() {
+    let inputs = vec!["a", "b"];
+    let metric = clustr::metric::Levenshtein::new(0.0).unwrap();
+    let result = clustr::cluster_strings_with_mode(&inputs, metric, clustr::Mode::SingleLinkage, 3);
+    assert_eq!(result, Err(clustr::ValueError::InsufficientWork));
+}
+
+#[test]
+fn test_complete_linkage_does_not_chain() {
+    use clustr::hierarchy::Linkage;
+    use clustr::metric::{Levenshtein, Metric};
+
+    let inputs = vec!["aaaa", "zzzz", "bbbb", "aabb", "zzzy", "abbb", "aaab"];
+    let metric = Levenshtein::new(0.25).unwrap();
+    for n_threads in 1..=4 {
+        let results =
+            clustr::cluster_strings_hierarchical(&inputs, 0.25, Linkage::Complete, n_threads)
+                .unwrap();
+        assert!(results.contains(&vec!["zzzz", "zzzy"]));
+        assert!(results.len() > 2);
+        for cluster in results {
+            for &a in &cluster {
+                for &b in &cluster {
+                    assert!(metric.is_similar(a, b));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hierarchical_cluster_count_validated() {
+    use clustr::hierarchy::{Cut, Linkage};
+
+    let inputs = vec!["a", "b"];
+    for k in [0, 3] {
+        let metric = clustr::metric::Levenshtein::new(0.0).unwrap();
+        let result = clustr::cluster_strings_hierarchical_with_metric(
+            &inputs,
+            metric,
+            Linkage::Ward,
+            Cut::Clusters(k),
+            1,
+        );
+        assert_eq!(result, Err(clustr::ValueError::InvalidClusterCount));
+    }
+
+    let metric = clustr::metric::Levenshtein::new(0.0).unwrap();
+    let result = clustr::cluster_strings_hierarchical_with_metric(
+        &inputs,
+        metric,
+        Linkage::Average,
+        Cut::Distance(f64::NAN),
+        1,
+    );
+    assert_eq!(result, Err(clustr::ValueError::InvalidCutDistance));
+}
+
+#[test]
+fn test_dendrogram_cuts_match_hierarchical_clustering() {
+    use clustr::hierarchy::{Cut, Linkage};
+
+    let inputs = vec![
+        "kitten", "sitting", "mitten", "smitten", "bitten", "knitting", "fitting",
+    ];
+    for linkage in [Linkage::Complete, Linkage::Average, Linkage::Ward] {
+        let dendrogram = clustr::cluster_strings_dendrogram(&inputs, linkage, 3).unwrap();
+        assert_eq!(dendrogram.steps().len(), inputs.len() - 1);
+        for max_edit_frac in [0.0, 0.2, 0.4, 0.6, 1.0] {
+            let expected =
+                clustr::cluster_strings_hierarchical(&inputs, max_edit_frac, linkage, 2).unwrap();
+            let results = dendrogram.cut(Cut::Distance(max_edit_frac as f64)).unwrap();
+            assert_eq!(results, expected);
+        }
+    }
+}
+
+#[test]
+fn test_linkage_matrix_finite_with_empty_strings() {
+    use clustr::hierarchy::{Cut, Linkage};
+
+    let inputs = vec!["", "", "a"];
+    let dendrogram = clustr::cluster_strings_dendrogram(&inputs, Linkage::Ward, 1).unwrap();
+    let matrix = dendrogram.linkage_matrix();
+    assert_eq!(matrix[0], [0.0, 1.0, 0.0, 2.0]);
+    assert!(matrix.iter().all(|row| row.iter().all(|x| x.is_finite())));
+    let results = dendrogram.cut(Cut::Distance(1.0)).unwrap();
+    assert_eq!(results, vec![vec!["", ""], vec!["a"]]);
+}
+
+#[test]
+fn test_ward_linkage_merges_infinitely_distant_clusters_last() {
+    use clustr::hierarchy::{Cut, Linkage};
+    use clustr::metric::{Hamming, LengthPolicy};
+
+    let inputs = vec!["aa", "ab", "aaa", "aab", "aaaa", "aaab"];
+    let metric = Hamming::new(0.0)
+        .unwrap()
+        .with_length_policy(LengthPolicy::Dissimilar);
+    let dendrogram =
+        clustr::cluster_strings_dendrogram_with_metric(&inputs, metric, Linkage::Ward, 2).unwrap();
+    let distances: Vec<f64> = dendrogram.steps().iter().map(|s| s.distance).collect();
+    assert_eq!(distances, vec![1.0, 1.0, 1.0, f64::INFINITY, f64::INFINITY]);
+    let results = dendrogram.cut(Cut::Distance(1.0)).unwrap();
+    assert_eq!(
+        results,
+        vec![vec!["aa", "ab"], vec!["aaa", "aab"], vec!["aaaa", "aaab"]]
+    );
+}
+
+#[test]
+fn test_dbscan_separates_noise() {
+    let inputs = vec![
+        "invoice", "x7#q", "invoices", "lnvoice", "receipt", "reciept", "receipts", "zz",
+    ];
+    let expected_clusters = vec![
+        vec!["invoice", "invoices", "lnvoice"],
+        vec!["receipt", "reciept", "receipts"],
+    ];
+    for n_threads in 1..=4 {
+        let result = clustr::cluster_strings_dbscan(&inputs, 0.3, 2, n_threads).unwrap();
+        assert_eq!(result.clusters, expected_clusters);
+        assert_eq!(result.noise, vec!["x7#q", "zz"]);
+    }
+}
+
+#[test]
+fn test_dbscan_validates_min_samples() {
+    let inputs = vec!["a", "b"];
+    let result = clustr::cluster_strings_dbscan(&inputs, 0.0, 0, 1);
+    assert_eq!(result, Err(clustr::ValueError::InvalidSampleCount));
+    // Inputs are validated first, as by every other entry point
+    let result = clustr::cluster_strings_dbscan(&inputs, 0.0, 0, 3);
+    assert_eq!(result, Err(clustr::ValueError::InsufficientWork));
+}
+
+#[test]
+fn test_kmedoids_returns_exactly_k_clusters() {
+    let inputs = vec![
+        "laptop", "laptops", "phone", "phones", "tablet", "tablets", "lap top", "iphone",
+    ];
+    for k in 1..=inputs.len() {
+        let result = clustr::cluster_strings_kmedoids(&inputs, k, 2).unwrap();
+        assert_eq!(result.medoids().len(), k);
+        assert_eq!(result.assignments().len(), inputs.len());
+        assert_eq!(
+            result.clusters().iter().map(Vec::len).sum::<usize>(),
+            inputs.len()
+        );
+    }
+
+    let result = clustr::cluster_strings_kmedoids(&inputs, 3, 4).unwrap();
+    assert_eq!(result.medoids(), vec!["laptop", "phone", "tablet"]);
+}
+
+#[test]
+fn test_kmedoids_validates_cluster_count() {
+    let inputs = vec!["a", "b"];
+    for k in [0, 3] {
+        let result = clustr::cluster_strings_kmedoids(&inputs, k, 1);
+        assert_eq!(result, Err(clustr::ValueError::InvalidClusterCount));
+    }
+}
+
+#[test]
+fn test_kmedoids_with_infinite_distances() {
+    use clustr::metric::{Hamming, LengthPolicy};
+
+    let inputs = vec!["ab", "abc", "abd", "abe", "zzz"];
+    let metric = Hamming::new(0.0)
+        .unwrap()
+        .with_length_policy(LengthPolicy::Dissimilar);
+    // "ab" stays infinitely distant from the medoid, which still minimises the
+    // distances of the others
+    let result = clustr::cluster_strings_kmedoids_with_metric(&inputs, metric, 1, 2).unwrap();
+    assert_eq!(result.medoids(), vec!["abc"]);
+    assert_eq!(result.cost(), f64::INFINITY);
+
+    let result = clustr::cluster_strings_kmedoids_with_metric(&inputs, metric, 2, 2).unwrap();
+    assert_eq!(result.medoids(), vec!["ab", "abc"]);
+    assert_eq!(result.cost(), 5.0);
+}